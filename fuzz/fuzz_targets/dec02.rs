@@ -0,0 +1,18 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Parse arbitrary input as the input of dec02 and solve both parts with every implementation.
+
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate common;
+extern crate dec02;
+
+use dec02::Dec02;
+
+fuzz_target!(|data: &[u8]| {
+    common::fuzz::pipeline::<Dec02>(data);
+});
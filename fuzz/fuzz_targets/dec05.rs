@@ -0,0 +1,18 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Parse arbitrary input as the input of dec05 and solve both parts with every implementation.
+
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate common;
+extern crate dec05;
+
+use dec05::Dec05;
+
+fuzz_target!(|data: &[u8]| {
+    common::fuzz::pipeline::<Dec05>(data);
+});
@@ -0,0 +1,24 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Parse every line of arbitrary input as a claim of dec03. The points of the claims that parse are
+// built as well, as that is where an oversized claim would hurt.
+
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate dec03;
+
+use std::str;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = str::from_utf8(data) {
+        for (index, line) in text.lines().enumerate() {
+            if let Ok(claim) = dec03::parse_claim(index + 1, line) {
+                claim.points();
+            }
+        }
+    }
+});
@@ -0,0 +1,21 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Parse every line of arbitrary input as an event of dec04.
+
+#![no_main]
+
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate dec04;
+
+use std::str;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = str::from_utf8(data) {
+        for (index, line) in text.lines().enumerate() {
+            let _ = dec04::parse(index + 1, line);
+        }
+    }
+});
@@ -0,0 +1,306 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// An on-disk cache of parsed puzzle inputs.
+//
+// Parsing large inputs can take much longer than solving them, so the parsed representation of an
+// input can be saved in a compact binary format and loaded again by later runs. Cache files are
+// named after the day and the FNV-1a hash of the input, such that a changed input simply misses
+// the cache. A cache file that cannot be decoded, or that was written by another version of the
+// file format or of the parser of the day, is treated as a miss and overwritten. The cache is
+// only an optimisation, so failing to write to it is a warning rather than an error.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use error::{Error, Result};
+use hash;
+use normalize;
+use Solution;
+
+// Identifies cache files written by us.
+const MAGIC: &[u8] = b"AOCCACHE";
+
+// Bump this whenever the layout of the cache files changes. Changes to a single day bump the
+// version of its parser instead, see `Cached::VERSION`.
+const VERSION: u32 = 2;
+
+// Makes the names of temporary files unique between threads.
+static TEMPORARY: AtomicUsize = AtomicUsize::new(0);
+
+// A solution whose parsed input can be saved in the cache.
+pub trait Cached: Solution {
+    // The version of the parser and the encoding of the day. Bump it whenever the parser accepts
+    // other inputs or produces another parsed input than before, or the encoding changes, such
+    // that inputs cached by earlier versions are parsed again.
+    const VERSION: u32;
+
+    fn encode(input: &Self::Input, encoder: &mut Encoder);
+
+    fn decode(decoder: &mut Decoder) -> Result<Self::Input>;
+}
+
+// Writes values in a fixed little endian layout.
+#[derive(Default)]
+pub struct Encoder {
+    data: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn new() -> Encoder {
+        Encoder::default()
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn i32(&mut self, value: i32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn usize(&mut self, value: usize) {
+        self.u64(value as u64);
+    }
+
+    pub fn str(&mut self, value: &str) {
+        self.usize(value.len());
+        self.data.extend_from_slice(value.as_bytes());
+    }
+}
+
+// Reads back the values written by an `Encoder`, in the same order.
+pub struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(data: &'a [u8]) -> Decoder<'a> {
+        Decoder { data }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8]> {
+        if length > self.data.len() {
+            return Err(Error::invalid("the cached input is truncated"));
+        }
+
+        let (value, rest) = self.data.split_at(length);
+        self.data = rest;
+
+        Ok(value)
+    }
+
+    pub fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn u32(&mut self) -> Result<u32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    pub fn i32(&mut self) -> Result<i32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(i32::from_le_bytes(bytes))
+    }
+
+    pub fn u64(&mut self) -> Result<u64> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    pub fn usize(&mut self) -> Result<usize> {
+        let value = self.u64()?;
+
+        // Lengths are checked against what is left such that a corrupt length cannot make us
+        // allocate huge vectors up front.
+        if value > self.data.len() as u64 {
+            return Err(Error::invalid("the cached input is truncated"));
+        }
+
+        Ok(value as usize)
+    }
+
+    pub fn str(&mut self) -> Result<&'a str> {
+        let length = self.usize()?;
+
+        ::std::str::from_utf8(self.take(length)?)
+            .map_err(|_| Error::invalid("the cached input contains invalid UTF-8"))
+    }
+
+    // Make sure everything has been read.
+    pub fn end(&self) -> Result<()> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::invalid("the cached input has trailing data"))
+        }
+    }
+}
+
+// A directory of cache files.
+pub struct Cache {
+    directory: PathBuf,
+}
+
+impl Cache {
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Cache {
+        Cache {
+            directory: directory.into(),
+        }
+    }
+
+    // The cache file of the given day and input hash.
+    pub fn path(&self, name: &str, key: u64) -> PathBuf {
+        self.directory.join(format!("{}-{:016x}.bin", name, key))
+    }
+
+    // The cached payload of the given day, version of its parser and input hash, if there is a
+    // valid one.
+    pub fn load(&self, name: &str, version: u32, key: u64) -> Option<Vec<u8>> {
+        let data = fs::read(self.path(name, key)).ok()?;
+        let mut decoder = Decoder::new(&data);
+
+        let valid = decoder.take(MAGIC.len()).ok()? == MAGIC
+            && decoder.u32().ok()? == VERSION
+            && decoder.str().ok()? == name
+            && decoder.u32().ok()? == version
+            && decoder.u64().ok()? == key;
+
+        if valid {
+            Some(decoder.data.to_vec())
+        } else {
+            None
+        }
+    }
+
+    // Save the payload of the given day, version of its parser and input hash. The file is written
+    // under a temporary name first, such that concurrent runs and threads never see a partially
+    // written file.
+    pub fn store(&self, name: &str, version: u32, key: u64, payload: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.directory)?;
+
+        let mut encoder = Encoder::new();
+        encoder.data.extend_from_slice(MAGIC);
+        encoder.u32(VERSION);
+        encoder.str(name);
+        encoder.u32(version);
+        encoder.u64(key);
+        encoder.data.extend_from_slice(payload);
+
+        let path = self.path(name, key);
+        let temporary = path.with_extension(format!(
+            "tmp.{}.{}",
+            process::id(),
+            TEMPORARY.fetch_add(1, Ordering::SeqCst)
+        ));
+
+        fs::write(&temporary, encoder.bytes())?;
+        fs::rename(&temporary, &path)
+    }
+}
+
+fn decode<S: Cached>(payload: &[u8]) -> Result<S::Input> {
+    let mut decoder = Decoder::new(payload);
+    let input = S::decode(&mut decoder)?;
+    decoder.end()?;
+
+    Ok(input)
+}
+
+// Parse the given input of the named day, using the cache when possible. A miss parses the input
+// as usual and saves the result for the next run, if it can.
+pub fn parse<S: Cached>(cache: &Cache, name: &str, data: &[u8]) -> Result<S::Input> {
+    let key = hash::fnv1a(data);
+
+    if let Some(payload) = cache.load(name, S::VERSION, key) {
+        if let Ok(input) = decode::<S>(&payload) {
+            return Ok(input);
+        }
+    }
+
+    let input = S::parse(&mut &data[..])?;
+
+    let mut encoder = Encoder::new();
+    S::encode(&input, &mut encoder);
+
+    if let Err(e) = cache.store(name, S::VERSION, key, encoder.bytes()) {
+        normalize::warn(format!(
+            "cannot save the parsed input in {}: {}",
+            cache.directory.display(),
+            e
+        ));
+    }
+
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    #[test]
+    fn encode_and_decode() {
+        let mut encoder = Encoder::new();
+        encoder.u8(7);
+        encoder.i32(-3);
+        encoder.usize(42);
+        encoder.str("abc");
+
+        let mut decoder = Decoder::new(encoder.bytes());
+        assert_eq!(decoder.u8().unwrap(), 7);
+        assert_eq!(decoder.i32().unwrap(), -3);
+        assert!(decoder.end().is_err());
+        assert_eq!(decoder.u64().unwrap(), 42);
+        assert_eq!(decoder.str().unwrap(), "abc");
+        assert!(decoder.end().is_ok());
+        assert!(decoder.u8().is_err());
+
+        // A length larger than what is left is rejected.
+        let mut encoder = Encoder::new();
+        encoder.usize(1000);
+        assert!(Decoder::new(encoder.bytes()).str().is_err());
+    }
+
+    #[test]
+    fn load_and_store() {
+        let directory = env::temp_dir().join(format!("aoc-cache-{}", process::id()));
+        let cache = Cache::new(&directory);
+
+        assert_eq!(cache.load("dec01", 1, 1), None);
+
+        cache.store("dec01", 1, 1, b"payload").unwrap();
+        assert_eq!(cache.load("dec01", 1, 1), Some(b"payload".to_vec()));
+        assert_eq!(cache.load("dec01", 1, 2), None);
+
+        // A file written by another version of the parser is not accepted.
+        assert_eq!(cache.load("dec01", 2, 1), None);
+
+        // A file written for another day under the same name is not accepted.
+        fs::copy(cache.path("dec01", 1), cache.path("dec02", 1)).unwrap();
+        assert_eq!(cache.load("dec02", 1, 1), None);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    // Reading the input failed.
+    Io(io::Error),
+
+    // A line of the input could not be parsed. Line and column numbers start at 1. The column is
+    // only known for errors reported by the parsing toolkit in `parse`.
+    Parse {
+        line: usize,
+        column: Option<usize>,
+        text: String,
+        message: String,
+    },
+
+    // The input was syntactically valid, but does not make sense for the given puzzle.
+    Invalid(String),
+}
+
+impl Error {
+    pub fn parse(line: usize, text: &str, message: &str) -> Error {
+        Error::Parse {
+            line,
+            column: None,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    // A syntax error at the given column of the given line, where we expected something else.
+    pub fn syntax(line: usize, column: usize, text: &str, expected: &str) -> Error {
+        Error::Parse {
+            line,
+            column: Some(column),
+            text: text.to_string(),
+            message: format!("expected {}", expected),
+        }
+    }
+
+    pub fn invalid<S: Into<String>>(message: S) -> Error {
+        Error::Invalid(message.into())
+    }
+
+    // The exit code a process should use when it fails with this error. Exit code 1 is left for
+    // generic failures and 2 for usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Io(_) => 3,
+            Error::Parse { .. } => 4,
+            Error::Invalid(_) => 5,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+            Error::Parse {
+                line,
+                column: None,
+                text,
+                message,
+            } => write!(f, "line {}: {}: {:?}", line, message, text),
+            Error::Parse {
+                line,
+                column: Some(column),
+                text,
+                message,
+            } => {
+                let (snippet, caret) = snippet(text, *column);
+                write!(
+                    f,
+                    "line {}, column {}: {}\n    {}\n    {:>width$}",
+                    line,
+                    column,
+                    message,
+                    snippet,
+                    "^",
+                    width = caret
+                )
+            }
+            Error::Invalid(message) => write!(f, "invalid input: {}", message),
+        }
+    }
+}
+
+// Number of characters shown on either side of the column of a syntax error.
+const SNIPPET_CONTEXT: usize = 30;
+
+// The part of the given line shown for a syntax error at the given column, and the column of the
+// caret within it. Long lines, such as the polymer of dec05, are cut down around the column.
+fn snippet(text: &str, column: usize) -> (String, usize) {
+    let characters: Vec<char> = text.chars().collect();
+    let index = column.saturating_sub(1);
+
+    let start = index.saturating_sub(SNIPPET_CONTEXT);
+    let end = (index + SNIPPET_CONTEXT + 1).min(characters.len());
+
+    let mut snippet = String::new();
+    let mut caret = index - start + 1;
+
+    if start > 0 {
+        snippet.push_str("...");
+        caret += 3;
+    }
+
+    snippet.extend(characters[start..end.max(start)].iter());
+
+    if end < characters.len() {
+        snippet.push_str("...");
+    }
+
+    (snippet, caret)
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let e = Error::parse(3, "+x", "invalid frequency change");
+        assert_eq!(e.to_string(), "line 3: invalid frequency change: \"+x\"");
+
+        let e = Error::syntax(1, 4, "#1 x 1,3: 4x4", "'@'");
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 4: expected '@'\n    #1 x 1,3: 4x4\n       ^"
+        );
+
+        let e = Error::invalid("no guard on duty");
+        assert_eq!(e.to_string(), "invalid input: no guard on duty");
+    }
+
+    #[test]
+    fn snippets() {
+        assert_eq!(snippet("abc", 4), (String::from("abc"), 4));
+
+        let long: String = (0..100).map(|_| 'a').collect();
+        let (text, caret) = snippet(&long, 50);
+        assert_eq!(text.len(), 3 + 61 + 3);
+        assert_eq!(caret, 3 + 31);
+    }
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let io = Error::from(io::Error::new(io::ErrorKind::NotFound, "gone"));
+        let parse = Error::parse(1, "", "empty line");
+        let invalid = Error::invalid("nope");
+
+        assert_ne!(io.exit_code(), parse.exit_code());
+        assert_ne!(io.exit_code(), invalid.exit_code());
+        assert_ne!(parse.exit_code(), invalid.exit_code());
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Example inputs from the puzzle descriptions live in the `examples` directory of each day crate.
+// An example consists of an input file, `NN.in`, and a file with the expected answers, `NN.out`.
+//
+// The expected answers file contains one answer per line in the form `<part> <answer>`. Empty
+// lines and lines starting with '#' are ignored. Only the parts listed in the file are checked,
+// since many of the examples only apply to one of the parts of a puzzle.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use error::{Error, Result};
+use input::Source;
+use {implementations, load, solve_with, Part, Solution};
+
+pub struct Example {
+    // The name of the example, for example "01".
+    name: String,
+
+    // The path of the input file.
+    input: PathBuf,
+
+    // The expected answers.
+    expected: Vec<(Part, String)>,
+}
+
+impl Example {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn input(&self) -> &Path {
+        &self.input
+    }
+
+    pub fn expected(&self) -> &[(Part, String)] {
+        &self.expected
+    }
+}
+
+// Find every example in the given directory, sorted by name.
+pub fn discover(directory: &Path) -> Result<Vec<Example>> {
+    let mut examples = Vec::new();
+
+    for entry in fs::read_dir(directory)? {
+        let input = entry?.path();
+
+        if input.extension().map(|e| e != "in").unwrap_or(true) {
+            continue;
+        }
+
+        let name = match input.file_stem().and_then(|s| s.to_str()) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        let output = input.with_extension("out");
+
+        if !output.exists() {
+            return Err(Error::invalid(format!(
+                "example {} has no expected answers in {}",
+                name,
+                output.display()
+            )));
+        }
+
+        let expected = parse_expected(&fs::read_to_string(&output)?)?;
+
+        examples.push(Example {
+            name,
+            input,
+            expected,
+        });
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(examples)
+}
+
+fn parse_expected(content: &str) -> Result<Vec<(Part, String)>> {
+    let mut expected = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.splitn(2, char::is_whitespace);
+
+        match (fields.next().map(|s| s.parse::<Part>()), fields.next()) {
+            (Some(Ok(part)), Some(answer)) => expected.push((part, answer.trim().to_string())),
+            _ => return Err(Error::parse(index + 1, line, "invalid expected answer")),
+        }
+    }
+
+    Ok(expected)
+}
+
+// Run the given example through every implementation of the given solution. Returns a description
+// of every implementation where the answer did not match the expected answer.
+pub fn run<S: Solution>(example: &Example) -> Result<Vec<String>> {
+    let input = load::<S>(&Source::Path(example.input.clone()))?;
+    let mut mismatches = Vec::new();
+
+    for (part, expected) in example.expected.iter() {
+        for (index, name) in implementations::<S>(*part).iter().enumerate() {
+            let answer = solve_with::<S>(&input, *part, index)?;
+
+            if answer != *expected {
+                mismatches.push(format!(
+                    "part {} ({}): expected {}, got {}",
+                    part, name, expected, answer
+                ));
+            }
+        }
+    }
+
+    Ok(mismatches)
+}
+
+// Run every example in the given directory through the given solution. Returns a description of
+// every failing example, or an error if there are no examples at all.
+pub fn check<S: Solution>(directory: &Path) -> Result<Vec<String>> {
+    let examples = discover(directory)?;
+
+    if examples.is_empty() {
+        return Err(Error::invalid(format!(
+            "no examples found in {}",
+            directory.display()
+        )));
+    }
+
+    let mut failures = Vec::new();
+
+    for example in examples.iter() {
+        match run::<S>(example) {
+            Ok(mismatches) => {
+                for mismatch in mismatches {
+                    failures.push(format!("example {} {}", example.name, mismatch));
+                }
+            }
+            Err(e) => failures.push(format!("example {}: {}", example.name, e)),
+        }
+    }
+
+    Ok(failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_expected_answers() {
+        let expected = parse_expected("# Comment\n1 3\n\n2 fgij\n").unwrap();
+
+        assert_eq!(
+            expected,
+            vec![
+                (Part::One, String::from("3")),
+                (Part::Two, String::from("fgij"))
+            ]
+        );
+
+        assert!(parse_expected("3 12\n").is_err());
+        assert!(parse_expected("1\n").is_err());
+    }
+}
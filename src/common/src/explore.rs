@@ -0,0 +1,62 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Interactive exploration of a parsed puzzle input. Each day offers a handful of commands mapped
+// onto the query methods of its data structures, such that the input only has to be parsed once
+// while poking around in it.
+
+use std::str::FromStr;
+
+use error::{Error, Result};
+use Solution;
+
+// The commands a day offers for exploring its parsed input.
+pub trait Explorer: Solution {
+    // The commands understood by `execute()` as pairs of usage and description. The first word of
+    // the usage is the name of the command, for example ("at X Y", "what is at the given point").
+    fn commands() -> Vec<(&'static str, &'static str)>;
+
+    // Run a single command against the parsed input and return what should be shown.
+    fn execute(input: &Self::Input, command: &str, args: &[&str]) -> Result<String>;
+}
+
+// The argument at the given index, parsed. `name` describes the argument in errors.
+pub fn argument<T: FromStr>(args: &[&str], index: usize, name: &str) -> Result<T> {
+    let arg = args
+        .get(index)
+        .ok_or_else(|| Error::invalid(format!("missing argument {}", name)))?;
+
+    arg.parse()
+        .map_err(|_| Error::invalid(format!("invalid {}: {}", name, arg)))
+}
+
+// The argument at the given index parsed, or `default` if it has not been given.
+pub fn optional<T: FromStr>(args: &[&str], index: usize, name: &str, default: T) -> Result<T> {
+    if index < args.len() {
+        argument(args, index, name)
+    } else {
+        Ok(default)
+    }
+}
+
+// The error for a command that the day does not know.
+pub fn unknown(command: &str) -> Error {
+    Error::invalid(format!("unknown command {}, try \"help\"", command))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_arguments() {
+        let args = ["3", "x"];
+
+        assert_eq!(argument::<u32>(&args, 0, "X").unwrap(), 3);
+        assert!(argument::<u32>(&args, 1, "Y").is_err());
+        assert!(argument::<u32>(&args, 2, "Z").is_err());
+        assert_eq!(optional(&args, 2, "Z", 7u32).unwrap(), 7);
+        assert_eq!(unknown("foo").exit_code(), 5);
+    }
+}
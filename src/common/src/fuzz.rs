@@ -0,0 +1,35 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Support for fuzzing the days.
+//
+// The fuzz targets in the `fuzz` directory at the root of the repository feed arbitrary bytes to
+// `pipeline()`, which parses them and runs every implementation and streaming part of a day. A
+// day may reject any input with an error, but must never panic on it. The corpus of the fuzz
+// targets is checked in and doubles as a regression test: the tests of the runner run every file
+// of the corpus of each registered day through the pipeline.
+
+use normalize;
+use {implementations, solve_with, stream, Part, Solution};
+
+// Parse the given data and compute both parts with every implementation, ignoring the answers and
+// errors. The warnings about the input are dropped as well.
+pub fn pipeline<S: Solution>(data: &[u8]) {
+    normalize::capture(|| {
+        for part in S::streaming_parts() {
+            let _ = stream::<S>(&mut &data[..], part);
+        }
+
+        let input = match S::parse(&mut &data[..]) {
+            Ok(input) => input,
+            Err(_) => return,
+        };
+
+        for part in Part::all() {
+            for index in 0..implementations::<S>(part).len() {
+                let _ = solve_with::<S>(&input, part, index);
+            }
+        }
+    });
+}
@@ -0,0 +1,211 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Synthetic puzzle inputs. Each day can generate inputs of arbitrary size in its own input format,
+// which is useful for stress testing the solutions with inputs much larger than the real ones.
+//
+// Generation is driven by a seeded random number generator, such that the same seed and knobs
+// always produce the same input on every platform.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::str::FromStr;
+
+use error::{Error, Result};
+
+// A generator of synthetic inputs for a single day.
+pub trait Generator {
+    // The knobs understood by `generate()` as pairs of name and description.
+    fn knobs() -> Vec<(&'static str, &'static str)>;
+
+    // Write a synthetic puzzle input to the given writer.
+    fn generate(rng: &mut Rng, knobs: &Knobs, writer: &mut dyn Write) -> Result<()>;
+}
+
+// A small SplitMix64 pseudo random number generator. It is not suitable for anything but
+// generating test data, but its output only depends on the seed.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A uniformly distributed value in the range `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0);
+
+        // Reject the values from the incomplete interval at the top of the range to avoid a bias
+        // towards the small values.
+        let limit = u64::MAX - u64::MAX % n;
+
+        loop {
+            let value = self.next_u64();
+
+            if value < limit {
+                return value % n;
+            }
+        }
+    }
+
+    // A uniformly distributed value in the inclusive range `low..=high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high);
+
+        let span = high.wrapping_sub(low) as u64;
+
+        if span == u64::MAX {
+            return self.next_u64() as i64;
+        }
+
+        low.wrapping_add(self.below(span + 1) as i64)
+    }
+
+    // Returns true with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    // Shuffle the given slice in place using the Fisher-Yates algorithm.
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            values.swap(i, j);
+        }
+    }
+}
+
+// The knobs of a generator, given as `name=value` pairs.
+pub struct Knobs {
+    values: HashMap<String, String>,
+}
+
+impl Knobs {
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Knobs> {
+        let mut values = HashMap::new();
+
+        for arg in args {
+            let arg = arg.as_ref();
+
+            match arg.find('=') {
+                Some(index) if index > 0 => {
+                    values.insert(arg[..index].to_string(), arg[index + 1..].to_string());
+                }
+                _ => {
+                    return Err(Error::invalid(format!(
+                        "invalid knob {}, expected NAME=VALUE",
+                        arg
+                    )))
+                }
+            }
+        }
+
+        Ok(Knobs { values })
+    }
+
+    // The names of the given knobs, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.values.keys().map(|s| s.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    // The value of the given knob, or `default` if it has not been set.
+    pub fn get<T: FromStr>(&self, name: &str, default: T) -> Result<T> {
+        match self.values.get(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| Error::invalid(format!("invalid value for knob {}: {}", name, value))),
+            None => Ok(default),
+        }
+    }
+
+    // The value of the given probability knob, which must be between 0 and 1.
+    pub fn probability(&self, name: &str, default: f64) -> Result<f64> {
+        let value = self.get(name, default)?;
+
+        if !(0.0..=1.0).contains(&value) {
+            return Err(Error::invalid(format!(
+                "knob {} must be between 0 and 1, got {}",
+                name, value
+            )));
+        }
+
+        Ok(value)
+    }
+}
+
+// Generate an input for the given day into memory. Mostly useful for tests.
+pub fn generate<G: Generator>(seed: u64, knobs: &Knobs) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    G::generate(&mut Rng::new(seed), knobs, &mut data)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_reproducible() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+
+        let a: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        let b: Vec<u64> = (0..8).map(|_| b.next_u64()).collect();
+        let c: Vec<u64> = (0..8).map(|_| c.next_u64()).collect();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        // The first output of SplitMix64 seeded with 0.
+        assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn rng_ranges() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..1000 {
+            assert!(rng.below(3) < 3);
+
+            let value = rng.range(-5, 5);
+            assert!((-5..=5).contains(&value));
+        }
+
+        assert_eq!(rng.range(7, 7), 7);
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+
+    #[test]
+    fn parse_knobs() {
+        let knobs = Knobs::parse(&["size=10", "overlap=0.5"]).unwrap();
+
+        assert_eq!(knobs.names(), vec!["overlap", "size"]);
+        assert_eq!(knobs.get("size", 1usize).unwrap(), 10);
+        assert_eq!(knobs.get("range", 3usize).unwrap(), 3);
+        assert_eq!(knobs.probability("overlap", 0.1).unwrap(), 0.5);
+
+        assert!(knobs.get("overlap", 1usize).is_err());
+        assert!(Knobs::parse(&["size"]).is_err());
+        assert!(Knobs::parse(&["=10"]).is_err());
+        assert!(Knobs::parse(&["p=2"])
+            .unwrap()
+            .probability("p", 0.5)
+            .is_err());
+    }
+}
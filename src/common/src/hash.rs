@@ -0,0 +1,104 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::io::{self, Read};
+
+// 64-bit FNV-1a hash. Unlike the hasher of the standard library, the output of FNV-1a is stable
+// across Rust versions and platforms, which makes it suitable for identifying inputs in reports
+// and on disk.
+pub struct Fnv1a {
+    hash: u64,
+}
+
+impl Fnv1a {
+    pub fn new() -> Fnv1a {
+        Fnv1a {
+            hash: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for byte in data {
+            self.hash ^= u64::from(*byte);
+            self.hash = self.hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    // The hash as a hexadecimal string.
+    pub fn hex(&self) -> String {
+        format!("{:016x}", self.hash)
+    }
+}
+
+impl Default for Fnv1a {
+    fn default() -> Fnv1a {
+        Fnv1a::new()
+    }
+}
+
+pub fn fnv1a(data: &[u8]) -> u64 {
+    let mut hasher = Fnv1a::new();
+    hasher.update(data);
+    hasher.finish()
+}
+
+// The FNV-1a hash of the given data as a hexadecimal string.
+pub fn hex(data: &[u8]) -> String {
+    format!("{:016x}", fnv1a(data))
+}
+
+// A reader computing the FNV-1a hash of everything read through it, such that inputs which are
+// never kept in memory as a whole can still be identified.
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Fnv1a,
+}
+
+impl<R: Read> HashingReader<R> {
+    pub fn new(inner: R) -> HashingReader<R> {
+        HashingReader {
+            inner,
+            hasher: Fnv1a::new(),
+        }
+    }
+
+    // The hash of the data read so far.
+    pub fn hasher(&self) -> &Fnv1a {
+        &self.hasher
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buffer)?;
+        self.hasher.update(&buffer[..n]);
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hex(b"foobar"), "85944171f73967e8");
+    }
+
+    #[test]
+    fn hashing_reader() {
+        let mut reader = HashingReader::new(&b"foobar"[..]);
+        let mut buffer = [0; 4];
+
+        while reader.read(&mut buffer).unwrap() > 0 {}
+
+        assert_eq!(reader.hasher().hex(), "85944171f73967e8");
+    }
+}
@@ -0,0 +1,400 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::str;
+
+use error::{Error, Result};
+use normalize::Normalizer;
+
+// Name of the environment variable pointing to a directory of puzzle inputs. The input of a given
+// day is expected to be found in `$AOC_INPUT_DIR/<year>/<name>.txt`, for example `2018/dec03.txt`.
+// The inputs of the first year may also be kept directly in the directory, as `dec03.txt`.
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+
+// The first year of puzzles, whose inputs were kept in `$AOC_INPUT_DIR/<name>.txt` before there
+// were more years.
+pub const FIRST_YEAR: u32 = 2018;
+
+// The default input file of a day, relative to its crate directory.
+pub const DEFAULT_INPUT: &str = "input/data.txt";
+
+// Where we read a puzzle input from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    // Read the input from the file at the given path.
+    Path(PathBuf),
+
+    // Read the input from the standard input.
+    Stdin,
+}
+
+impl Source {
+    // Figure out where the input of the day of the given year and name should be read from. An
+    // explicit argument always wins: "-" selects the standard input and anything else is a path.
+    // Without an argument we look for `$AOC_INPUT_DIR/<year>/<name>.txt`, or for the flat
+    // `$AOC_INPUT_DIR/<name>.txt` if only that exists and the day is of the first year, and
+    // finally fall back to the given default path if `AOC_INPUT_DIR` is not set.
+    pub fn resolve(argument: Option<&str>, year: u32, name: &str, default: &str) -> Source {
+        let directory = env::var_os(INPUT_DIR_VARIABLE).map(PathBuf::from);
+        select(argument, directory, year, name, default)
+    }
+
+    // Resolve the input source of a day binary using its first command line argument.
+    pub fn from_args(year: u32, name: &str) -> Source {
+        let argument = env::args().nth(1);
+        Source::resolve(argument.as_deref(), year, name, DEFAULT_INPUT)
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Source::Path(path) => {
+                let f = File::open(path)?;
+                Ok(Box::new(BufReader::new(f)))
+            }
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    // Read the whole input into memory.
+    pub fn read(&self) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.open()?.read_to_end(&mut data)?;
+
+        Ok(data)
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+// An iterator over the records of a puzzle input with one record per line. Every line is
+// normalised, see `normalize`, and then parsed using the given function, which is called with the
+// line number and the text of the line, as it is read. Only a single line is kept in memory at a
+// time, except for runs of blank lines, which are held back until we know whether they are at the
+// end of the input and should be dropped. The iterator stops at the first error.
+pub struct Records<'a, F> {
+    reader: &'a mut dyn BufRead,
+    buffer: Vec<u8>,
+    line: usize,
+    normalizer: Normalizer,
+
+    // The first line and the number of the blank lines held back.
+    blank: Option<(usize, usize)>,
+
+    // A line read after the blank lines, which is parsed once they have been.
+    held: Option<(usize, String)>,
+
+    done: bool,
+    parse: F,
+}
+
+impl<'a, F> Records<'a, F> {
+    // Read and normalise the next line. A line that is not valid UTF-8 is a syntax error at the
+    // first invalid byte.
+    fn read(&mut self) -> Result<Option<String>> {
+        self.buffer.clear();
+
+        if self.reader.read_until(b'\n', &mut self.buffer)? == 0 {
+            return Ok(None);
+        }
+
+        if self.buffer.ends_with(b"\n") {
+            self.buffer.pop();
+        }
+
+        self.line += 1;
+
+        let text = str::from_utf8(&self.buffer).map_err(|e| {
+            let valid = str::from_utf8(&self.buffer[..e.valid_up_to()]).unwrap_or("");
+            let text = String::from_utf8_lossy(&self.buffer);
+
+            Error::syntax(self.line, valid.chars().count() + 1, &text, "valid UTF-8")
+        })?;
+
+        Ok(Some(self.normalizer.line(self.line, text).to_string()))
+    }
+
+    // Stop at the end of the input or at the first error and report what was removed from the
+    // lines read so far. The warnings are reported on errors as well, since a carriage return or
+    // a byte order mark often explains why a line was rejected.
+    fn stop(&mut self) {
+        self.done = true;
+        self.blank = None;
+        self.held = None;
+        self.normalizer.finish();
+    }
+}
+
+impl<'a, T, F> Iterator for Records<'a, F>
+where
+    F: FnMut(usize, &str) -> Result<T>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.advance();
+
+        if let Some(Err(_)) = result {
+            self.stop();
+        }
+
+        result
+    }
+}
+
+impl<'a, T, F> Records<'a, F>
+where
+    F: FnMut(usize, &str) -> Result<T>,
+{
+    fn advance(&mut self) -> Option<Result<T>> {
+        // Blank lines followed by more records are not ours to drop. They are handed to the
+        // parser one at a time before the record that followed them, which will usually reject
+        // them.
+        if self.held.is_some() {
+            if let Some((first, count)) = self.blank.take() {
+                if count > 1 {
+                    self.blank = Some((first + 1, count - 1));
+                }
+
+                return Some((self.parse)(first, ""));
+            }
+
+            let (line, text) = self.held.take().unwrap();
+            return Some((self.parse)(line, &text));
+        }
+
+        loop {
+            let text = match self.read() {
+                Ok(Some(text)) => text,
+                Ok(None) => break,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if text.is_empty() {
+                let line = self.line;
+                self.blank = Some(self.blank.map_or((line, 1), |(first, n)| (first, n + 1)));
+                continue;
+            }
+
+            if self.blank.is_some() {
+                self.held = Some((self.line, text));
+                return self.advance();
+            }
+
+            return Some((self.parse)(self.line, &text));
+        }
+
+        if let Some((_, count)) = self.blank.take() {
+            self.normalizer.trailing_blank_lines(count);
+        }
+
+        self.stop();
+
+        None
+    }
+}
+
+// Lazily parse the records of the given reader, see `Records`.
+pub fn records<T, F>(reader: &mut dyn BufRead, parse: F) -> Records<'_, F>
+where
+    F: FnMut(usize, &str) -> Result<T>,
+{
+    Records {
+        reader,
+        buffer: Vec::new(),
+        line: 0,
+        normalizer: Normalizer::new(),
+        blank: None,
+        held: None,
+        done: false,
+        parse,
+    }
+}
+
+fn select(
+    argument: Option<&str>,
+    directory: Option<PathBuf>,
+    year: u32,
+    name: &str,
+    default: &str,
+) -> Source {
+    let directory = match (argument, directory) {
+        (Some("-"), _) => return Source::Stdin,
+        (Some(path), _) => return Source::Path(PathBuf::from(path)),
+        (None, Some(directory)) => directory,
+        (None, None) => return Source::Path(PathBuf::from(default)),
+    };
+
+    let path = directory
+        .join(year.to_string())
+        .join(format!("{}.txt", name));
+    let flat = directory.join(format!("{}.txt", name));
+
+    if year == FIRST_YEAR && !path.exists() && flat.exists() {
+        Source::Path(flat)
+    } else {
+        Source::Path(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn parse_records() {
+        let mut reader = "1\n2\nx\n".as_bytes();
+        let mut records = records(&mut reader, |line, text| {
+            text.parse::<u32>()
+                .map_err(|_| ::Error::parse(line, text, "not a number"))
+        });
+
+        assert_eq!(records.next().unwrap().unwrap(), 1);
+        assert_eq!(records.next().unwrap().unwrap(), 2);
+
+        match records.next() {
+            Some(Err(::Error::Parse { line, .. })) => assert_eq!(line, 3),
+            _ => panic!("expected a parse error"),
+        }
+
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn normalize_records() {
+        let mut reader = "1\r\n\n\n2 \n\n\n".as_bytes();
+        let (lines, warnings) = ::normalize::capture(|| {
+            records(&mut reader, |line, text| Ok((line, text.to_string())))
+                .collect::<Result<Vec<_>>>()
+                .unwrap()
+        });
+
+        let expected: Vec<(usize, String)> = vec![(1, "1"), (2, ""), (3, ""), (4, "2")]
+            .into_iter()
+            .map(|(line, text)| (line, text.to_string()))
+            .collect();
+
+        assert_eq!(lines, expected);
+        assert_eq!(
+            warnings,
+            vec![
+                "line 1: removed a carriage return",
+                "line 4: removed trailing whitespace",
+                "removed 2 blank lines at the end",
+            ]
+        );
+    }
+
+    #[test]
+    fn report_warnings_on_errors() {
+        let mut reader = "1\r\n2\r\nx\r\n4\r\n".as_bytes();
+        let (result, warnings) = ::normalize::capture(|| {
+            records(&mut reader, |line, text| {
+                text.parse::<u32>()
+                    .map_err(|_| ::Error::parse(line, text, "not a number"))
+            })
+            .collect::<Result<Vec<_>>>()
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            warnings,
+            vec!["line 1: removed a carriage return (and on 2 more lines)"]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_utf8() {
+        let mut reader = &b"abc\nd\xffe\n"[..];
+        let mut records = records(&mut reader, |_, text| Ok(text.to_string()));
+
+        assert_eq!(records.next().unwrap().unwrap(), "abc");
+
+        match records.next() {
+            Some(Err(::Error::Parse { line, column, .. })) => {
+                assert_eq!((line, column), (2, Some(2)))
+            }
+            _ => panic!("expected a syntax error"),
+        }
+
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn select_source() {
+        let directory = Some(PathBuf::from("/inputs"));
+
+        assert_eq!(
+            select(Some("-"), directory.clone(), 2018, "dec01", DEFAULT_INPUT),
+            Source::Stdin
+        );
+        assert_eq!(
+            select(
+                Some("foo.txt"),
+                directory.clone(),
+                2018,
+                "dec01",
+                DEFAULT_INPUT
+            ),
+            Source::Path(PathBuf::from("foo.txt"))
+        );
+        assert_eq!(
+            select(None, directory.clone(), 2018, "dec01", DEFAULT_INPUT),
+            Source::Path(PathBuf::from("/inputs/2018/dec01.txt"))
+        );
+        assert_eq!(
+            select(None, directory, 2019, "dec01", DEFAULT_INPUT),
+            Source::Path(PathBuf::from("/inputs/2019/dec01.txt"))
+        );
+        assert_eq!(
+            select(None, None, 2018, "dec01", DEFAULT_INPUT),
+            Source::Path(PathBuf::from(DEFAULT_INPUT))
+        );
+    }
+
+    #[test]
+    fn select_flat_source() {
+        let directory = env::temp_dir().join(format!("aoc-inputs-{}", process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("dec01.txt"), "+1\n").unwrap();
+
+        // Only the first year falls back to the flat layout, and only if its input is not found
+        // in the directory of the year.
+        assert_eq!(
+            select(None, Some(directory.clone()), 2018, "dec01", DEFAULT_INPUT),
+            Source::Path(directory.join("dec01.txt"))
+        );
+        assert_eq!(
+            select(None, Some(directory.clone()), 2019, "dec01", DEFAULT_INPUT),
+            Source::Path(directory.join("2019/dec01.txt"))
+        );
+
+        fs::create_dir_all(directory.join("2018")).unwrap();
+        fs::write(directory.join("2018/dec01.txt"), "+1\n").unwrap();
+        assert_eq!(
+            select(None, Some(directory.clone()), 2018, "dec01", DEFAULT_INPUT),
+            Source::Path(directory.join("2018/dec01.txt"))
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
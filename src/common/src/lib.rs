@@ -0,0 +1,208 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+pub mod cache;
+pub mod error;
+pub mod examples;
+pub mod explore;
+pub mod fuzz;
+pub mod generate;
+pub mod hash;
+pub mod input;
+pub mod metadata;
+pub mod normalize;
+pub mod parse;
+pub mod trace;
+pub mod visualize;
+
+use std::fmt::{self, Display};
+use std::io::BufRead;
+use std::process;
+use std::str::FromStr;
+
+pub use error::{Error, Result};
+use input::Source;
+
+// A solution to a single day of the Advent of Code calendar.
+//
+// Every day follows the same shape: the puzzle input is parsed once into `Input` and both parts of
+// the puzzle are then computed from the parsed input.
+pub trait Solution {
+    // The parsed representation of the puzzle input shared by both parts.
+    type Input;
+
+    // The answer of part 1.
+    type Output1: Display;
+
+    // The answer of part 2.
+    type Output2: Display;
+
+    // Parse the puzzle input from the given reader.
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input>;
+
+    // Compute the answer of part 1.
+    fn part1(input: &Self::Input) -> Result<Self::Output1>;
+
+    // Compute the answer of part 2.
+    fn part2(input: &Self::Input) -> Result<Self::Output2>;
+
+    // Every implementation of part 1. The first implementation is the default used for normal
+    // runs, while the others are alternative algorithms that must always agree with it.
+    fn part1_implementations() -> Vec<Implementation<Self::Input, Self::Output1>> {
+        vec![Implementation::new("default", Self::part1)]
+    }
+
+    // Every implementation of part 2, see `part1_implementations()`.
+    fn part2_implementations() -> Vec<Implementation<Self::Input, Self::Output2>> {
+        vec![Implementation::new("default", Self::part2)]
+    }
+
+    // The parts that can be computed in a single pass over the puzzle input in constant memory,
+    // using `stream_part1()` and `stream_part2()`, rather than by parsing the whole input first.
+    fn streaming_parts() -> Vec<Part> {
+        Vec::new()
+    }
+
+    // Compute the answer of part 1 while reading the puzzle input from the given reader. Only
+    // called if `streaming_parts()` contains part 1.
+    fn stream_part1(_reader: &mut dyn BufRead) -> Result<Self::Output1> {
+        Err(Error::invalid("part 1 cannot be computed in a single pass"))
+    }
+
+    // Compute the answer of part 2 while reading the puzzle input, see `stream_part1()`.
+    fn stream_part2(_reader: &mut dyn BufRead) -> Result<Self::Output2> {
+        Err(Error::invalid("part 2 cannot be computed in a single pass"))
+    }
+}
+
+// A named implementation of one of the parts of a solution.
+pub struct Implementation<I, O> {
+    name: &'static str,
+    function: fn(&I) -> Result<O>,
+}
+
+impl<I, O> Implementation<I, O> {
+    pub fn new(name: &'static str, function: fn(&I) -> Result<O>) -> Implementation<I, O> {
+        Implementation { name, function }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn run(&self, input: &I) -> Result<O> {
+        (self.function)(input)
+    }
+}
+
+// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+impl Part {
+    pub fn all() -> Vec<Part> {
+        vec![Part::One, Part::Two]
+    }
+}
+
+impl FromStr for Part {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Part, ()> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+// Open the given input source and parse it using the given solution.
+pub fn load<S: Solution>(source: &Source) -> Result<S::Input> {
+    let mut reader = source.open()?;
+
+    S::parse(&mut reader)
+}
+
+// The names of the implementations of the given part. The first name is the default.
+pub fn implementations<S: Solution>(part: Part) -> Vec<&'static str> {
+    match part {
+        Part::One => S::part1_implementations()
+            .iter()
+            .map(|i| i.name())
+            .collect(),
+        Part::Two => S::part2_implementations()
+            .iter()
+            .map(|i| i.name())
+            .collect(),
+    }
+}
+
+// Compute the answer of the given part using the implementation at the given index of
+// `implementations()` and format it for display.
+pub fn solve_with<S: Solution>(input: &S::Input, part: Part, index: usize) -> Result<String> {
+    match part {
+        Part::One => Ok(S::part1_implementations()[index].run(input)?.to_string()),
+        Part::Two => Ok(S::part2_implementations()[index].run(input)?.to_string()),
+    }
+}
+
+// Compute the answer of the given part using the default implementation.
+pub fn solve<S: Solution>(input: &S::Input, part: Part) -> Result<String> {
+    solve_with::<S>(input, part, 0)
+}
+
+// Compute the answer of the given part in a single pass over the given reader and format it for
+// display. See `Solution::streaming_parts()`.
+pub fn stream<S: Solution>(reader: &mut dyn BufRead, part: Part) -> Result<String> {
+    match part {
+        Part::One => Ok(S::stream_part1(reader)?.to_string()),
+        Part::Two => Ok(S::stream_part2(reader)?.to_string()),
+    }
+}
+
+// Print the given error and terminate the process with the exit code matching the error.
+pub fn exit(source: &Source, error: &Error) -> ! {
+    eprintln!("{}: {}", source, error);
+    process::exit(error.exit_code());
+}
+
+// Load the input of the day of the given year and name and print the answer to both parts of the
+// given solution. The input source is taken from the command line, see `Source::from_args()`.
+pub fn run<S: Solution>(year: u32, name: &str) {
+    let source = Source::from_args(year, name);
+    let result = load::<S>(&source).and_then(|input| {
+        println!("Result of task 1: {}", solve::<S>(&input, Part::One)?);
+        println!("Result of task 2: {}", solve::<S>(&input, Part::Two)?);
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        exit(&source, &e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_from_str() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert_eq!("3".parse::<Part>(), Err(()));
+    }
+}
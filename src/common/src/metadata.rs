@@ -0,0 +1,64 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Metadata about the puzzles. The name of a crate only says which day it solves, so each day also
+// describes its puzzle: the title, what each part asks for and what the input looks like. The
+// runner, its reports and the scaffolding of new days present the days using this.
+
+use std::fmt;
+
+use Part;
+
+// The kind of value a part asks for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Answer {
+    // A number, such as a frequency or a count.
+    Integer,
+
+    // A piece of text, such as the common letters of two box IDs.
+    Text,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer => write!(f, "integer"),
+            Answer::Text => write!(f, "text"),
+        }
+    }
+}
+
+// The description of a puzzle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Puzzle {
+    // The year of the event and the day of the month.
+    pub year: u32,
+    pub day: u32,
+
+    // The title of the puzzle, such as "Chronal Calibration".
+    pub title: &'static str,
+
+    // What each part asks for and the kind of its answer, in the order of the parts.
+    pub parts: [(&'static str, Answer); 2],
+
+    // What the input looks like, in a sentence.
+    pub input: &'static str,
+}
+
+impl Puzzle {
+    // What the given part asks for.
+    pub fn part(&self, part: Part) -> &'static str {
+        self.parts[part as usize - 1].0
+    }
+
+    // The kind of answer the given part asks for.
+    pub fn answer(&self, part: Part) -> Answer {
+        self.parts[part as usize - 1].1
+    }
+}
+
+// A day describing its puzzle.
+pub trait Metadata {
+    fn puzzle() -> Puzzle;
+}
@@ -0,0 +1,205 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Normalisation of puzzle inputs.
+//
+// Inputs that have been through an editor or a browser on another platform often differ from the
+// original in harmless ways: a byte order mark at the start, CRLF line endings, trailing
+// whitespace or blank lines at the end. Every line of an input goes through a `Normalizer` before
+// it is parsed, which removes these and reports what it removed as warnings, such that the
+// parsers only ever see clean lines.
+//
+// Warnings are printed to the standard error unless `capture()` is running on the current thread,
+// which allows the runner to show them together with the input they belong to.
+
+use std::cell::RefCell;
+use std::io::BufRead;
+
+use error::Result;
+use input::records;
+
+thread_local! {
+    static WARNINGS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+// Report a warning about an input.
+pub fn warn(message: String) {
+    let message = WARNINGS.with(|warnings| match warnings.borrow_mut().as_mut() {
+        Some(warnings) => {
+            warnings.push(message);
+            None
+        }
+        None => Some(message),
+    });
+
+    if let Some(message) = message {
+        eprintln!("warning: {}", message);
+    }
+}
+
+// Run the given closure while collecting the warnings it reports and return them together with
+// its result.
+pub fn capture<T, F: FnOnce() -> T>(f: F) -> (T, Vec<String>) {
+    let previous = WARNINGS.with(|warnings| warnings.replace(Some(Vec::new())));
+    let result = f();
+    let captured = WARNINGS.with(|warnings| warnings.replace(previous));
+
+    (result, captured.unwrap_or_default())
+}
+
+// The first line a kind of problem was seen on and how many lines had it.
+#[derive(Default)]
+struct Tally {
+    first: usize,
+    count: usize,
+}
+
+impl Tally {
+    fn add(&mut self, line: usize) {
+        if self.count == 0 {
+            self.first = line;
+        }
+
+        self.count += 1;
+    }
+
+    fn warning(&self, what: &str) -> Option<String> {
+        match self.count {
+            0 => None,
+            1 => Some(format!("line {}: removed {}", self.first, what)),
+            2 => Some(format!(
+                "line {}: removed {} (and on 1 more line)",
+                self.first, what
+            )),
+            n => Some(format!(
+                "line {}: removed {} (and on {} more lines)",
+                self.first,
+                what,
+                n - 1
+            )),
+        }
+    }
+}
+
+// Cleans up the lines of a single input, one line at a time.
+#[derive(Default)]
+pub struct Normalizer {
+    byte_order_mark: bool,
+    carriage_returns: Tally,
+    trailing_whitespace: Tally,
+    trailing_blank_lines: usize,
+}
+
+impl Normalizer {
+    pub fn new() -> Normalizer {
+        Normalizer::default()
+    }
+
+    // Clean up the line with the given number. The text must not include the line feed.
+    pub fn line<'a>(&mut self, number: usize, text: &'a str) -> &'a str {
+        let mut text = text;
+
+        if number == 1 {
+            if let Some(rest) = text.strip_prefix('\u{feff}') {
+                self.byte_order_mark = true;
+                text = rest;
+            }
+        }
+
+        if let Some(rest) = text.strip_suffix('\r') {
+            self.carriage_returns.add(number);
+            text = rest;
+        }
+
+        let trimmed = text.trim_end();
+
+        if trimmed.len() != text.len() {
+            self.trailing_whitespace.add(number);
+        }
+
+        trimmed
+    }
+
+    // Record that the given number of blank lines at the end of the input were dropped.
+    pub fn trailing_blank_lines(&mut self, count: usize) {
+        self.trailing_blank_lines += count;
+    }
+
+    // The warnings about everything that was removed so far.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if self.byte_order_mark {
+            warnings.push(String::from("line 1: removed a byte order mark"));
+        }
+
+        warnings.extend(self.carriage_returns.warning("a carriage return"));
+        warnings.extend(self.trailing_whitespace.warning("trailing whitespace"));
+
+        match self.trailing_blank_lines {
+            0 => {}
+            1 => warnings.push(String::from("removed a blank line at the end")),
+            n => warnings.push(format!("removed {} blank lines at the end", n)),
+        }
+
+        warnings
+    }
+
+    // Report the warnings.
+    pub fn finish(&self) {
+        for warning in self.warnings() {
+            warn(warning);
+        }
+    }
+}
+
+// Read the whole input as normalised text, for puzzles that are not line based. The lines are
+// joined by line feeds and the text does not end with one.
+pub fn text(reader: &mut dyn BufRead) -> Result<String> {
+    let lines = records(reader, |_, text| Ok(text.to_string())).collect::<Result<Vec<_>>>()?;
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_lines() {
+        let mut normalizer = Normalizer::new();
+
+        assert_eq!(normalizer.line(1, "\u{feff}abc\r"), "abc");
+        assert_eq!(normalizer.line(2, "def \t\r"), "def");
+        assert_eq!(normalizer.line(3, "ghi"), "ghi");
+        assert_eq!(normalizer.line(4, "\u{feff}x"), "\u{feff}x");
+        normalizer.trailing_blank_lines(2);
+
+        assert_eq!(
+            normalizer.warnings(),
+            vec![
+                "line 1: removed a byte order mark",
+                "line 1: removed a carriage return (and on 1 more line)",
+                "line 2: removed trailing whitespace",
+                "removed 2 blank lines at the end",
+            ]
+        );
+
+        assert!(Normalizer::new().warnings().is_empty());
+    }
+
+    #[test]
+    fn normalize_text() {
+        let (result, warnings) = capture(|| text(&mut "\u{feff}dabAc\r\n\r\n\n".as_bytes()));
+
+        assert_eq!(result.unwrap(), "dabAc");
+        assert_eq!(warnings.len(), 3);
+
+        // A missing final line feed is not worth a warning.
+        let (result, warnings) = capture(|| text(&mut "dabAc".as_bytes()));
+
+        assert_eq!(result.unwrap(), "dabAc");
+        assert!(warnings.is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// A small toolkit for parsing the lines of puzzle inputs.
+//
+// A `Cursor` walks over a single line of input. Every method either consumes what it expects from
+// the current position or returns a syntax error pointing at the column where the input went
+// wrong together with what was expected there.
+
+use std::str::FromStr;
+
+use error::{Error, Result};
+
+pub struct Cursor<'a> {
+    // The line number of the line, used for errors.
+    line: usize,
+
+    // The whole line.
+    text: &'a str,
+
+    // The byte offset of the current position within `text`.
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(line: usize, text: &'a str) -> Cursor<'a> {
+        Cursor {
+            line,
+            text,
+            position: 0,
+        }
+    }
+
+    // The part of the line that has yet to be parsed.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    // The column of the current position. Columns start at 1.
+    pub fn column(&self) -> usize {
+        self.text[..self.position].chars().count() + 1
+    }
+
+    // A syntax error at the current position.
+    pub fn error(&self, expected: &str) -> Error {
+        Error::syntax(self.line, self.column(), self.text, expected)
+    }
+
+    // Consume the given separator or keyword.
+    pub fn tag(&mut self, tag: &str) -> Result<()> {
+        if self.try_tag(tag) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("{:?}", tag)))
+        }
+    }
+
+    // Consume the given separator or keyword if it is next. Returns whether it was.
+    pub fn try_tag(&mut self, tag: &str) -> bool {
+        if self.rest().starts_with(tag) {
+            self.position += tag.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    // Skip any whitespace.
+    pub fn whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    // Consume the given separator, allowing whitespace on either side of it.
+    pub fn separator(&mut self, tag: &str) -> Result<()> {
+        self.whitespace();
+        self.tag(tag)?;
+        self.whitespace();
+        Ok(())
+    }
+
+    // Consume at least one character matching the given predicate and return them.
+    pub fn take_while<P>(&mut self, expected: &str, predicate: P) -> Result<&'a str>
+    where
+        P: Fn(char) -> bool,
+    {
+        let rest = self.rest();
+        let length = rest
+            .char_indices()
+            .find(|(_, c)| !predicate(*c))
+            .map_or(rest.len(), |(index, _)| index);
+
+        if length == 0 {
+            return Err(self.error(expected));
+        }
+
+        self.position += length;
+        Ok(&rest[..length])
+    }
+
+    // Consume an unsigned integer.
+    pub fn unsigned<T: FromStr>(&mut self) -> Result<T> {
+        let start = self.position;
+        let digits = self.take_while("a number", |c| c.is_ascii_digit())?;
+
+        digits.parse().map_err(|_| {
+            self.position = start;
+            self.error("a number that fits")
+        })
+    }
+
+    // Consume an integer with an optional sign, such as "+3" or "-7".
+    pub fn signed<T: FromStr>(&mut self) -> Result<T> {
+        let start = self.position;
+
+        if !self.try_tag("+") {
+            self.try_tag("-");
+        }
+
+        self.take_while("a number", |c| c.is_ascii_digit())?;
+
+        // The standard library accepts the leading '+' as well.
+        self.text[start..self.position].parse().map_err(|_| {
+            self.position = start;
+            self.error("a number that fits")
+        })
+    }
+
+    // Consume an unsigned integer within the given inclusive range. `name` describes the number
+    // in errors.
+    pub fn bounded(&mut self, name: &str, low: u32, high: u32) -> Result<u32> {
+        let start = self.position;
+        let value = self.unsigned::<u32>()?;
+
+        if value < low || value > high {
+            self.position = start;
+            return Err(self.error(&format!("a {} between {} and {}", name, low, high)));
+        }
+
+        Ok(value)
+    }
+
+    // Consume a date in the form "YYYY-MM-DD" and return the year, month and day.
+    pub fn date(&mut self) -> Result<(i32, u32, u32)> {
+        let year = self.unsigned::<i32>()?;
+        self.tag("-")?;
+        let month = self.bounded("month", 1, 12)?;
+        self.tag("-")?;
+        let day = self.bounded("day", 1, 31)?;
+
+        Ok((year, month, day))
+    }
+
+    // Consume a time in the form "HH:MM" and return the hour and minute.
+    pub fn time(&mut self) -> Result<(u32, u32)> {
+        let hour = self.bounded("hour", 0, 23)?;
+        self.tag(":")?;
+        let minute = self.bounded("minute", 0, 59)?;
+
+        Ok((hour, minute))
+    }
+
+    // Make sure the whole line has been consumed.
+    pub fn end(&self) -> Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("the end of the line"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(result: Result<()>) -> Option<usize> {
+        match result {
+            Err(Error::Parse { column, .. }) => column,
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        let mut cursor = Cursor::new(1, "+12 -3 7 99999999999");
+
+        assert_eq!(cursor.signed::<i32>().unwrap(), 12);
+        cursor.whitespace();
+        assert_eq!(cursor.signed::<i32>().unwrap(), -3);
+        cursor.whitespace();
+        assert_eq!(cursor.unsigned::<u8>().unwrap(), 7);
+        cursor.whitespace();
+        assert_eq!(column(cursor.unsigned::<u32>().map(|_| ())), Some(10));
+    }
+
+    #[test]
+    fn dates_and_times() {
+        let mut cursor = Cursor::new(1, "1518-11-01 00:05");
+
+        assert_eq!(cursor.date().unwrap(), (1518, 11, 1));
+        cursor.tag(" ").unwrap();
+        assert_eq!(cursor.time().unwrap(), (0, 5));
+        assert!(cursor.end().is_ok());
+
+        let mut cursor = Cursor::new(1, "1518-13-01");
+        assert_eq!(column(cursor.date().map(|_| ())), Some(6));
+    }
+
+    #[test]
+    fn errors_point_at_the_column() {
+        let mut cursor = Cursor::new(4, "#1 @ 1;3");
+
+        cursor.tag("#").unwrap();
+        cursor.unsigned::<u32>().unwrap();
+        cursor.separator("@").unwrap();
+        cursor.unsigned::<u32>().unwrap();
+
+        match cursor.tag(",") {
+            Err(Error::Parse {
+                line,
+                column,
+                message,
+                ..
+            }) => {
+                assert_eq!(line, 4);
+                assert_eq!(column, Some(7));
+                assert_eq!(message, "expected \",\"");
+            }
+            _ => panic!("expected a syntax error"),
+        }
+
+        assert_eq!(column(Cursor::new(1, "abc").end()), Some(1));
+        assert!(Cursor::new(1, "").take_while("x", |_| true).is_err());
+    }
+}
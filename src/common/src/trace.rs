@@ -0,0 +1,96 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Trace events explaining how an answer was reached, such as the guard that was picked or the
+// pair of box IDs that matched.
+//
+// Solutions emit events using the `trace!` macro. Events are only recorded while `capture()` is
+// running on the current thread, so tracing costs next to nothing otherwise. Keeping the events
+// per thread means that days running in parallel do not see each other's events.
+
+use std::cell::RefCell;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    // What happened, for example "guard chosen".
+    pub message: String,
+
+    // The details of the event as pairs of name and value.
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+
+        for (name, value) in self.fields.iter() {
+            write!(f, " {}={}", name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+thread_local! {
+    static EVENTS: RefCell<Option<Vec<Event>>> = const { RefCell::new(None) };
+}
+
+// Whether events are currently being recorded on this thread.
+pub fn enabled() -> bool {
+    EVENTS.with(|events| events.borrow().is_some())
+}
+
+// Record an event if events are being recorded. Use `trace!` rather than calling this directly.
+pub fn event(message: &str, fields: Vec<(&'static str, String)>) {
+    EVENTS.with(|events| {
+        if let Some(events) = events.borrow_mut().as_mut() {
+            events.push(Event {
+                message: message.to_string(),
+                fields,
+            });
+        }
+    });
+}
+
+// Run the given closure while recording the events it emits and return them together with its
+// result.
+pub fn capture<T, F: FnOnce() -> T>(f: F) -> (T, Vec<Event>) {
+    let previous = EVENTS.with(|events| events.replace(Some(Vec::new())));
+    let result = f();
+    let captured = EVENTS.with(|events| events.replace(previous));
+
+    (result, captured.unwrap_or_default())
+}
+
+// Emit a trace event with a message and any number of `name => value` fields. The values are only
+// formatted if events are being recorded.
+#[macro_export]
+macro_rules! trace {
+    ($message:expr $(, $name:expr => $value:expr)* $(,)*) => {
+        if $crate::trace::enabled() {
+            $crate::trace::event($message, vec![$(($name, $value.to_string())),*]);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_events() {
+        trace!("ignored");
+
+        let (result, events) = capture(|| {
+            trace!("guard chosen", "guard" => 10, "minute" => 24);
+            42
+        });
+
+        assert_eq!(result, 42);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].to_string(), "guard chosen guard=10 minute=24");
+        assert!(!enabled());
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Visualisations of parsed puzzle inputs. A day can draw figures of its input, such as the claims
+// on the fabric of dec03, which the runner embeds in its HTML report. The figures are SVG images
+// drawn with the small `Svg` writer below, such that the report is a single file that does not
+// need anything but a browser.
+
+use std::fmt::Write;
+
+use Solution;
+
+// A drawing of some aspect of a parsed input.
+pub struct Figure {
+    pub title: String,
+
+    // The drawing itself as an `<svg>` element.
+    pub svg: String,
+}
+
+// The figures a day draws of its parsed input. Most days have nothing worth drawing, so there are
+// none by default.
+pub trait Visualizer: Solution {
+    fn figures(_input: &Self::Input) -> Vec<Figure> {
+        Vec::new()
+    }
+}
+
+// Escape text for use in HTML and SVG, both in elements and in quoted attributes.
+pub fn escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+// The colour of the given value between 0 and 1 on a scale from pale yellow through orange to
+// dark red, as used for heat maps.
+pub fn heat(value: f64) -> String {
+    const STOPS: [(f64, f64, f64); 3] = [
+        (255.0, 247.0, 188.0),
+        (252.0, 141.0, 89.0),
+        (127.0, 0.0, 0.0),
+    ];
+
+    let value = value.clamp(0.0, 1.0) * 2.0;
+    let index = (value as usize).min(1);
+    let t = value - index as f64;
+    let (from, to) = (STOPS[index], STOPS[index + 1]);
+    let mix = |a: f64, b: f64| (a + (b - a) * t).round() as u8;
+
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        mix(from.0, to.0),
+        mix(from.1, to.1),
+        mix(from.2, to.2)
+    )
+}
+
+// Writes an SVG image one element at a time. Coordinates are in pixels from the top left corner.
+pub struct Svg {
+    width: f64,
+    height: f64,
+    body: String,
+}
+
+impl Svg {
+    pub fn new(width: f64, height: f64) -> Svg {
+        Svg {
+            width,
+            height,
+            body: String::new(),
+        }
+    }
+
+    // A filled rectangle. A non-empty title is shown when hovering over it.
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str, title: &str) {
+        let _ = write!(
+            self.body,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"",
+            x, y, width, height, fill
+        );
+
+        if title.is_empty() {
+            self.body.push_str("/>");
+        } else {
+            let _ = write!(self.body, "><title>{}</title></rect>", escape(title));
+        }
+    }
+
+    // The outline of a rectangle.
+    pub fn frame(&mut self, x: f64, y: f64, width: f64, height: f64, stroke: &str) {
+        let _ = write!(
+            self.body,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\" \
+             stroke-width=\"2\"/>",
+            x, y, width, height, stroke
+        );
+    }
+
+    // A line of text. `anchor` is "start", "middle" or "end" and says which part of the text is
+    // placed at `x`.
+    pub fn text(&mut self, x: f64, y: f64, anchor: &str, text: &str) {
+        let _ = write!(
+            self.body,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"{}\">{}</text>",
+            x,
+            y,
+            anchor,
+            escape(text)
+        );
+    }
+
+    pub fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"11\">{}</svg>",
+            self.body,
+            w = self.width,
+            h = self.height
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_text() {
+        assert_eq!(escape("#1 @ 1,3: 4x4"), "#1 @ 1,3: 4x4");
+        assert_eq!(
+            escape("<a href=\"x\">'&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn heat_scale() {
+        assert_eq!(heat(0.0), "#fff7bc");
+        assert_eq!(heat(0.5), "#fc8d59");
+        assert_eq!(heat(1.0), "#7f0000");
+        assert_eq!(heat(-1.0), heat(0.0));
+        assert_eq!(heat(2.0), heat(1.0));
+    }
+
+    #[test]
+    fn draw_svg() {
+        let mut svg = Svg::new(20.0, 10.0);
+        svg.rect(0.0, 0.0, 5.0, 5.0, "#000000", "");
+        svg.rect(5.0, 0.0, 5.0, 5.0, "red", "a < b");
+        svg.text(10.0, 10.0, "middle", "x");
+
+        assert_eq!(
+            svg.finish(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"10\" \
+             viewBox=\"0 0 20 10\" font-family=\"sans-serif\" font-size=\"11\">\
+             <rect x=\"0\" y=\"0\" width=\"5\" height=\"5\" fill=\"#000000\"/>\
+             <rect x=\"5\" y=\"0\" width=\"5\" height=\"5\" fill=\"red\"><title>a &lt; b</title></rect>\
+             <text x=\"10\" y=\"10\" text-anchor=\"middle\">x</text></svg>"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::collections::LinkedList;
+
+use common::cache::{Cached, Decoder, Encoder};
+use common::Result;
+
+use Dec01;
+
+impl Cached for Dec01 {
+    const VERSION: u32 = 1;
+
+    fn encode(values: &LinkedList<i32>, encoder: &mut Encoder) {
+        encoder.usize(values.len());
+
+        for value in values {
+            encoder.i32(*value);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<LinkedList<i32>> {
+        let length = decoder.usize()?;
+
+        (0..length).map(|_| decoder.i32()).collect()
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::collections::LinkedList;
+
+use common::explore::{self, Explorer};
+use common::Result;
+
+use Dec01;
+
+impl Explorer for Dec01 {
+    fn commands() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("changes", "number of frequency changes"),
+            ("change N", "the Nth frequency change"),
+            (
+                "frequency [N]",
+                "the frequency after the first N changes (default all)",
+            ),
+        ]
+    }
+
+    fn execute(values: &LinkedList<i32>, command: &str, args: &[&str]) -> Result<String> {
+        match command {
+            "changes" => Ok(values.len().to_string()),
+            "change" => {
+                let n: usize = explore::argument(args, 0, "N")?;
+
+                match values.iter().nth(n.wrapping_sub(1)) {
+                    Some(value) => Ok(format!("{:+}", value)),
+                    None => Ok(format!("there are only {} changes", values.len())),
+                }
+            }
+            "frequency" => {
+                let n = explore::optional(args, 0, "N", values.len())?;
+                let frequency: i64 = values.iter().take(n).map(|value| i64::from(*value)).sum();
+
+                Ok(frequency.to_string())
+            }
+            _ => Err(explore::unknown(command)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explore_changes() {
+        let values: LinkedList<i32> = vec![1, -2, 3, 1].into_iter().collect();
+
+        assert_eq!(Dec01::execute(&values, "changes", &[]).unwrap(), "4");
+        assert_eq!(Dec01::execute(&values, "change", &["2"]).unwrap(), "-2");
+        assert_eq!(Dec01::execute(&values, "frequency", &["2"]).unwrap(), "-1");
+        assert_eq!(Dec01::execute(&values, "frequency", &[]).unwrap(), "3");
+        assert!(Dec01::execute(&values, "foo", &[]).is_err());
+    }
+}
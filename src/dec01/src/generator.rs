@@ -0,0 +1,100 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::io::Write;
+
+use common::generate::{Generator, Knobs, Rng};
+use common::{Error, Result};
+
+use Dec01;
+
+impl Generator for Dec01 {
+    fn knobs() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("size", "number of frequency changes (default 1000)"),
+            ("range", "largest absolute value of a change (default 1000)"),
+        ]
+    }
+
+    fn generate(rng: &mut Rng, knobs: &Knobs, writer: &mut dyn Write) -> Result<()> {
+        let size = knobs.get("size", 1000u64)?;
+        let range = knobs.get("range", 1000i64)?;
+
+        if size == 0 || range <= 0 {
+            return Err(Error::invalid("size and range must be positive"));
+        }
+
+        // Every change must fit in the 32 bits the parser reads it into.
+        if range > i64::from(i32::MAX) {
+            return Err(Error::invalid(format!(
+                "range must be at most {}",
+                i32::MAX
+            )));
+        }
+
+        let mut frequency: i64 = 0;
+
+        for _ in 1..size {
+            let mut change = rng.range(1, range);
+
+            if rng.chance(0.5) {
+                change = -change;
+            }
+
+            frequency += change;
+            writeln!(writer, "{:+}", change)?;
+        }
+
+        // The last change brings the frequency to a small drift, which is the answer to part 1.
+        // Part 2 has no answer if the frequency never repeats, but the frequencies reached before
+        // each of the `size` changes fall into fewer than `size` residue classes modulo the drift,
+        // so two of them share a class and one of them is reached again in a later pass.
+        let mut drift = rng.range(0, range.min(size as i64 - 1));
+
+        if rng.chance(0.5) {
+            drift = -drift;
+        }
+
+        let last = drift - frequency;
+
+        if last.abs() > i64::from(i32::MAX) {
+            return Err(Error::invalid("the last change does not fit in 32 bits"));
+        }
+
+        writeln!(writer, "{:+}", last)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::generate;
+    use common::Solution;
+
+    #[test]
+    fn generated_input_parses() {
+        let knobs = Knobs::parse(&["size=500", "range=50"]).unwrap();
+        let data = generate::generate::<Dec01>(7, &knobs).unwrap();
+
+        assert_eq!(data, generate::generate::<Dec01>(7, &knobs).unwrap());
+
+        let values = Dec01::parse(&mut &data[..]).unwrap();
+        assert_eq!(values.len(), 500);
+        assert!(Dec01::part1(&values).unwrap().abs() <= 50);
+        assert!(Dec01::part2(&values).is_ok());
+    }
+
+    #[test]
+    fn range_fits_the_parser() {
+        let knobs = Knobs::parse(&["size=2", "range=5000000000"]).unwrap();
+        assert!(generate::generate::<Dec01>(7, &knobs).is_err());
+
+        let knobs = Knobs::parse(&["size=1", "range=2147483647"]).unwrap();
+        let data = generate::generate::<Dec01>(7, &knobs).unwrap();
+        assert!(Dec01::parse(&mut &data[..]).is_ok());
+    }
+}
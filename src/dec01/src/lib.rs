@@ -0,0 +1,258 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+#[macro_use]
+extern crate common;
+
+mod cache;
+mod explore;
+mod generator;
+mod metadata;
+
+use std::collections::{HashMap, HashSet, LinkedList};
+use std::io::BufRead;
+
+use common::input::records;
+use common::parse::Cursor;
+use common::visualize::Visualizer;
+use common::{Error, Part, Result, Solution};
+
+// Parse a single frequency change such as "+3" or "-7".
+fn parse_change(line: usize, text: &str) -> Result<i32> {
+    let mut cursor = Cursor::new(line, text);
+    let value = cursor.signed()?;
+    cursor.end()?;
+
+    Ok(value)
+}
+
+pub fn compute_result_task1(values: &LinkedList<i32>) -> i64 {
+    let frequency: i64 = values.iter().map(|value| i64::from(*value)).sum();
+    trace!("resulting frequency", "changes" => values.len(), "frequency" => frequency);
+
+    frequency
+}
+
+// The first frequency that is reached twice while the changes are applied over and over again.
+//
+// Applying the changes until that happens can take arbitrarily many passes, or never end at all,
+// so we only follow the first pass. Every later pass visits the same frequencies moved by the
+// resulting frequency of a pass, `drift`. A frequency seen in a later pass therefore repeats a
+// frequency of the first pass that is a multiple of `drift` away from it in the direction of the
+// drift, and the closest such frequency tells us in which pass that happens.
+pub fn compute_result_task2(values: &LinkedList<i32>) -> Result<i64> {
+    let n = values.len();
+
+    // The frequencies before each change of the first pass. The first repeat is within the first
+    // pass if there is one there.
+    let mut frequencies: Vec<i64> = Vec::with_capacity(n);
+    let mut seen = HashSet::new();
+    let mut frequency: i64 = 0;
+
+    for value in values.iter() {
+        if !seen.insert(frequency) {
+            return Ok(repeated(frequency, frequencies.len() as u128, n));
+        }
+
+        frequencies.push(frequency);
+        frequency += i64::from(*value);
+    }
+
+    let drift = frequency;
+
+    if n == 0 {
+        return Err(Error::invalid("there are no frequency changes"));
+    }
+
+    if drift == 0 {
+        return Ok(repeated(0, n as u128, n));
+    }
+
+    // Frequencies can only ever meet if they are in the same residue class modulo the drift. Sort
+    // each class by frequency, such that the frequency to meet is the next one in the class.
+    let mut classes: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+
+    for (index, frequency) in frequencies.iter().enumerate() {
+        classes
+            .entry(frequency.rem_euclid(drift))
+            .or_default()
+            .push((*frequency, index));
+    }
+
+    // The earliest (iteration, frequency) at which a frequency is reached again.
+    let mut first: Option<(u128, i64)> = None;
+
+    for class in classes.values_mut() {
+        class.sort();
+
+        for pair in class.windows(2) {
+            let ((low, low_index), (high, high_index)) = (pair[0], pair[1]);
+            let passes = ((high - low) / drift.abs()) as u128;
+
+            let (index, frequency) = if drift > 0 {
+                (low_index, high)
+            } else {
+                (high_index, low)
+            };
+            let iteration = passes * n as u128 + index as u128;
+
+            if first.is_none_or(|(earliest, _)| iteration < earliest) {
+                first = Some((iteration, frequency));
+            }
+        }
+    }
+
+    match first {
+        Some((iteration, frequency)) => Ok(repeated(frequency, iteration, n)),
+        None => Err(Error::invalid("the frequency never repeats")),
+    }
+}
+
+// Report the frequency that was reached twice after the given number of changes.
+fn repeated(frequency: i64, iteration: u128, n: usize) -> i64 {
+    trace!(
+        "frequency repeated",
+        "frequency" => frequency,
+        "iteration" => iteration,
+        "pass" => (iteration - 1) / n as u128 + 1,
+        "change" => (iteration - 1) % n as u128 + 1
+    );
+
+    frequency
+}
+
+pub struct Dec01;
+
+impl Solution for Dec01 {
+    type Input = LinkedList<i32>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<LinkedList<i32>> {
+        records(reader, parse_change).collect()
+    }
+
+    fn part1(values: &LinkedList<i32>) -> Result<i64> {
+        Ok(compute_result_task1(values))
+    }
+
+    fn part2(values: &LinkedList<i32>) -> Result<i64> {
+        compute_result_task2(values)
+    }
+
+    // The resulting frequency only depends on the sum of the changes, while part 2 needs to
+    // cycle through all of them.
+    fn streaming_parts() -> Vec<Part> {
+        vec![Part::One]
+    }
+
+    fn stream_part1(reader: &mut dyn BufRead) -> Result<i64> {
+        records(reader, |line, text| parse_change(line, text).map(i64::from)).sum()
+    }
+}
+
+// The frequency changes have nothing worth drawing.
+impl Visualizer for Dec01 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compute_test_result_task1(values: &[i32]) -> i64 {
+        let mut v = LinkedList::new();
+        v.extend(values);
+
+        compute_result_task1(&v)
+    }
+
+    #[test]
+    fn known_results_task1() {
+        assert_eq!(3, compute_test_result_task1(&[1, 1, 1]));
+        assert_eq!(0, compute_test_result_task1(&[1, 1, -2]));
+        assert_eq!(-6, compute_test_result_task1(&[-1, -2, -3]));
+    }
+
+    fn compute_test_result_task2(values: &[i32]) -> Result<i64> {
+        let mut v = LinkedList::new();
+        v.extend(values);
+
+        compute_result_task2(&v)
+    }
+
+    #[test]
+    fn known_results_task2() {
+        assert_eq!(0, compute_test_result_task2(&[1, -1]).unwrap());
+        assert_eq!(10, compute_test_result_task2(&[3, 3, 4, -2, -4]).unwrap());
+        assert_eq!(5, compute_test_result_task2(&[-6, 3, 8, 5, -6]).unwrap());
+        assert_eq!(14, compute_test_result_task2(&[7, 7, -2, -7, -4]).unwrap());
+    }
+
+    #[test]
+    fn task2_without_simulation() {
+        // The frequency only repeats after a billion passes.
+        assert_eq!(
+            1_000_000_000,
+            compute_test_result_task2(&[1_000_000_000, -999_999_999]).unwrap()
+        );
+
+        assert!(compute_test_result_task2(&[]).is_err());
+        assert!(compute_test_result_task2(&[1]).is_err());
+        assert!(compute_test_result_task2(&[3, -1]).is_err());
+    }
+
+    #[test]
+    fn task2_matches_simulation() {
+        // Apply the changes until a frequency repeats, for inputs where that happens quickly.
+        fn simulate(values: &[i32]) -> i64 {
+            let mut frequency = 0;
+            let mut seen = HashSet::new();
+            seen.insert(frequency);
+
+            for value in values.iter().cycle() {
+                frequency += i64::from(*value);
+
+                if !seen.insert(frequency) {
+                    break;
+                }
+            }
+
+            frequency
+        }
+
+        let mut rng = common::generate::Rng::new(1);
+
+        for _ in 0..1000 {
+            let n = rng.range(1, 8) as usize;
+            let values: Vec<i32> = (0..n).map(|_| rng.range(-10, 10) as i32).collect();
+
+            match compute_test_result_task2(&values) {
+                Ok(frequency) => assert_eq!(frequency, simulate(&values), "{:?}", values),
+                Err(_) => assert!(values.iter().sum::<i32>() != 0, "{:?}", values),
+            }
+        }
+    }
+
+    #[test]
+    fn stream_part1_matches_part1() {
+        let input = "+1\n-2\n+3\n+1\n";
+        let values = Dec01::parse(&mut input.as_bytes()).unwrap();
+
+        assert_eq!(
+            Dec01::stream_part1(&mut input.as_bytes()).unwrap(),
+            Dec01::part1(&values).unwrap()
+        );
+        assert!(Dec01::stream_part1(&mut "+1\nfoo\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn parse_reports_invalid_line() {
+        match Dec01::parse(&mut "+1\n-2\nfoo\n".as_bytes()) {
+            Err(Error::Parse { line, text, .. }) => {
+                assert_eq!(line, 3);
+                assert_eq!(text, "foo");
+            }
+            _ => panic!("expected a parse error"),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::metadata::{Answer, Metadata, Puzzle};
+
+use Dec01;
+
+impl Metadata for Dec01 {
+    fn puzzle() -> Puzzle {
+        Puzzle {
+            year: 2018,
+            day: 1,
+            title: "Chronal Calibration",
+            parts: [
+                ("the resulting frequency after all changes", Answer::Integer),
+                ("the first frequency reached twice", Answer::Integer),
+            ],
+            input: "one frequency change per line, a sign followed by a number such as +7 or -3",
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::cache::{Cached, Decoder, Encoder};
+use common::Result;
+
+use Dec02;
+
+impl Cached for Dec02 {
+    const VERSION: u32 = 1;
+
+    fn encode(lines: &Vec<String>, encoder: &mut Encoder) {
+        encoder.usize(lines.len());
+
+        for line in lines {
+            encoder.str(line);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Vec<String>> {
+        let length = decoder.usize()?;
+
+        (0..length)
+            .map(|_| decoder.str().map(|line| line.to_string()))
+            .collect()
+    }
+}
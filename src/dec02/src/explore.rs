@@ -0,0 +1,68 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::collections::HashMap;
+
+use common::explore::{self, Explorer};
+use common::{Error, Result};
+
+use {check_strings, Dec02};
+
+// The box ID with the given number. Box IDs are numbered from 1 in the order of the input.
+fn box_id<'a>(lines: &'a [String], args: &[&str], index: usize, name: &str) -> Result<&'a str> {
+    let n: usize = explore::argument(args, index, name)?;
+
+    lines
+        .get(n.wrapping_sub(1))
+        .map(|line| line.as_str())
+        .ok_or_else(|| Error::invalid(format!("there are only {} box IDs", lines.len())))
+}
+
+impl Explorer for Dec02 {
+    fn commands() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("ids", "number of box IDs"),
+            (
+                "id N",
+                "the Nth box ID and whether any letter appears twice or thrice",
+            ),
+            (
+                "compare N M",
+                "the letters the Nth and Mth box IDs have in common if they differ by one",
+            ),
+        ]
+    }
+
+    fn execute(lines: &Vec<String>, command: &str, args: &[&str]) -> Result<String> {
+        match command {
+            "ids" => Ok(lines.len().to_string()),
+            "id" => {
+                let id = box_id(lines, args, 0, "N")?;
+
+                let mut frequency = HashMap::new();
+
+                for c in id.chars() {
+                    *frequency.entry(c).or_insert(0) += 1;
+                }
+
+                Ok(format!(
+                    "{} twice={} thrice={}",
+                    id,
+                    frequency.values().any(|&f| f == 2),
+                    frequency.values().any(|&f| f == 3)
+                ))
+            }
+            "compare" => {
+                let a = box_id(lines, args, 0, "N")?;
+                let b = box_id(lines, args, 1, "M")?;
+
+                match check_strings(a, b) {
+                    Some(common) => Ok(common),
+                    None => Ok("the box IDs differ by more than one letter".to_string()),
+                }
+            }
+            _ => Err(explore::unknown(command)),
+        }
+    }
+}
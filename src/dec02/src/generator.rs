@@ -0,0 +1,127 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::collections::HashSet;
+use std::io::Write;
+
+use common::generate::{Generator, Knobs, Rng};
+use common::{Error, Result};
+
+use Dec02;
+
+// Number of attempts at generating a box ID that does not resemble any previous box ID.
+const ATTEMPTS: usize = 1000;
+
+// Every variant of the given box ID where one of the letters is masked out. Two box IDs of the
+// same length differ by at most one letter iff they share a variant.
+fn variants(id: &[u8]) -> Vec<Vec<u8>> {
+    (0..id.len())
+        .map(|index| {
+            let mut variant = id.to_vec();
+            variant[index] = b'@';
+            variant
+        })
+        .collect()
+}
+
+fn random_id(rng: &mut Rng, length: usize, letters: u64) -> Vec<u8> {
+    (0..length)
+        .map(|_| b'a' + rng.below(letters) as u8)
+        .collect()
+}
+
+impl Generator for Dec02 {
+    fn knobs() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("size", "number of box IDs (default 250)"),
+            ("length", "length of each box ID (default 26)"),
+            (
+                "letters",
+                "number of distinct letters, fewer gives more doubles and triples (default 26)",
+            ),
+        ]
+    }
+
+    fn generate(rng: &mut Rng, knobs: &Knobs, writer: &mut dyn Write) -> Result<()> {
+        let size = knobs.get("size", 250usize)?;
+        let length = knobs.get("length", 26usize)?;
+        let letters = knobs.get("letters", 26u64)?;
+
+        if size < 2 || length < 2 || !(2..=26).contains(&letters) {
+            return Err(Error::invalid(
+                "size and length must be at least 2 and letters between 2 and 26",
+            ));
+        }
+
+        // Generate box IDs that differ from every other box ID by at least two letters, such that
+        // the pair planted below is the only answer to part 2.
+        let mut seen = HashSet::new();
+        let mut ids = Vec::with_capacity(size);
+
+        for _ in 0..size - 1 {
+            let id = (0..ATTEMPTS)
+                .map(|_| random_id(rng, length, letters))
+                .find(|id| variants(id).iter().all(|v| !seen.contains(v)))
+                .ok_or_else(|| {
+                    Error::invalid("too few letters to generate that many distinct box IDs")
+                })?;
+
+            seen.extend(variants(&id));
+            ids.push(id);
+        }
+
+        // Plant the pair of box IDs differing by exactly one letter. The changed letter may still
+        // make the copy resemble a third box ID, so we retry until it does not.
+        let original = ids[rng.below(ids.len() as u64) as usize].clone();
+        let own = variants(&original);
+
+        let copy = (0..ATTEMPTS)
+            .map(|_| {
+                let mut copy = original.clone();
+                let index = rng.below(length as u64) as usize;
+                let offset = 1 + rng.below(letters - 1) as u8;
+                copy[index] = b'a' + (copy[index] - b'a' + offset) % letters as u8;
+                copy
+            })
+            .find(|copy| {
+                variants(copy)
+                    .iter()
+                    .all(|v| own.contains(v) || !seen.contains(v))
+            })
+            .ok_or_else(|| Error::invalid("too few letters to plant a unique pair of box IDs"))?;
+
+        let position = rng.below(size as u64) as usize;
+        ids.insert(position, copy);
+
+        for id in ids {
+            writer.write_all(&id)?;
+            writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::generate;
+    use common::Solution;
+
+    #[test]
+    fn generated_input_parses() {
+        let knobs = Knobs::parse(&["size=300", "length=8", "letters=6"]).unwrap();
+        let data = generate::generate::<Dec02>(7, &knobs).unwrap();
+
+        assert_eq!(data, generate::generate::<Dec02>(7, &knobs).unwrap());
+
+        let lines = Dec02::parse(&mut &data[..]).unwrap();
+        assert_eq!(lines.len(), 300);
+
+        let answer = Dec02::part2(&lines).unwrap();
+        assert_eq!(answer.len(), 7);
+        assert_eq!(answer, ::compute_result_task2_naive(&lines));
+    }
+}
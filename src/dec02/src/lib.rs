@@ -0,0 +1,314 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+#[macro_use]
+extern crate common;
+
+mod cache;
+mod explore;
+mod generator;
+mod metadata;
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::vec::Vec;
+
+use common::input::records;
+use common::parse::Cursor;
+use common::visualize::Visualizer;
+use common::{Error, Implementation, Part, Result, Solution};
+
+// The checksum of a list of box IDs, computed one box ID at a time.
+#[derive(Default)]
+pub struct Checksum {
+    // Number of box IDs where any letter appears exactly twice.
+    two: i32,
+
+    // Number of box IDs where any letter appears exactly thrice.
+    three: i32,
+
+    // Frequency of each letter of the current box ID.
+    letter_frequency: HashMap<char, usize>,
+}
+
+impl Checksum {
+    pub fn new() -> Checksum {
+        Checksum::default()
+    }
+
+    pub fn add(&mut self, line: &str) {
+        // Build a table containing the frequency of each letter.
+        for c in line.chars() {
+            *self.letter_frequency.entry(c).or_insert(0) += 1
+        }
+
+        // Increment our `two` count by one iff any letter appears twice.
+        if self.letter_frequency.values().any(|&f| f == 2) {
+            self.two += 1
+        }
+
+        // Increment our `three` count by one iff any letter appears thrice.
+        if self.letter_frequency.values().any(|&f| f == 3) {
+            self.three += 1
+        }
+
+        self.letter_frequency.clear();
+    }
+
+    pub fn value(&self) -> i32 {
+        trace!("checksum", "twice" => self.two, "thrice" => self.three);
+
+        self.two * self.three
+    }
+}
+
+pub fn compute_result_task1(lines: &[String]) -> i32 {
+    let mut checksum = Checksum::new();
+
+    for line in lines {
+        checksum.add(line);
+    }
+
+    checksum.value()
+}
+
+// Returns the letters `a` and `b` have in common if they differ in exactly one position. Box IDs
+// of different lengths never match.
+pub fn check_strings(a: &str, b: &str) -> Option<String> {
+    if a.len() != b.len() {
+        return None;
+    }
+
+    // Build a sequence of pairs with the letter of each string.
+    let letters = a.chars().zip(b.chars());
+
+    // Build a new string where letters at the same position of `a` and `b` are removed.
+    let result: String = letters
+        .filter_map(|(v_a, v_b)| if v_a == v_b { Some(v_a) } else { None })
+        .collect();
+
+    // If our stripped string in `result` have one less character than the input, we have the
+    // result.
+    if result.len() + 1 == a.len() {
+        return Some(result);
+    }
+
+    // This was not the result we were looking for.
+    None
+}
+
+pub fn compute_result_task2_naive(lines: &[String]) -> String {
+    // Naive O(n^2) algorithm.
+    for (index, a) in lines.iter().enumerate() {
+        for b in lines.iter().skip(index + 1) {
+            if let Some(result) = check_strings(a, b) {
+                trace!("box IDs matched", "first" => a, "second" => b, "common" => result);
+                return result;
+            }
+        }
+    }
+
+    String::from("Not found")
+}
+
+pub fn compute_result_task2_linear(lines: &[String]) -> String {
+    // Linear algorithm. This is the default implementation, see `part2_implementations()`.
+    let mut seen = HashMap::new();
+
+    for line in lines {
+        // We use '@' as replacement character, which `parse_box_id()` makes sure never appears in
+        // the input.
+        //
+        // For each character in the line, create a new string where a character is replaced by
+        // '@'.
+        //
+        // For example:
+        //   "foobar" -> ["@oobar", "f@obar", "fo@bar", "foo@ar", "foob@r", "fooba@"].
+        for (index, _) in line.chars().enumerate() {
+            let new_line: String = line
+                .chars()
+                .enumerate()
+                .map(|(i, c)| if i == index { '@' } else { c })
+                .collect();
+
+            // If our mutated string already exists for a different box ID, remove all '@' from
+            // the string and return the result. A duplicate of a box ID differs from it in no
+            // letters rather than one, so it is not a match, just like in the naive algorithm.
+            if let Some(other) = seen
+                .insert(new_line.clone(), line)
+                .filter(|other| *other != line)
+            {
+                let result: String = new_line.chars().filter(|c| *c != '@').collect();
+                trace!(
+                    "box IDs matched",
+                    "first" => other,
+                    "second" => line,
+                    "common" => result,
+                    "position" => index
+                );
+                return result;
+            }
+        }
+    }
+
+    String::from("Not found")
+}
+
+// Parse a single box ID. `length` is the length of the first box ID, which every other box ID must
+// match.
+fn parse_box_id(line: usize, text: &str, length: &mut Option<usize>) -> Result<String> {
+    // Box IDs are made of lowercase letters. This also guarantees that the '@' replacement
+    // character used by `compute_result_task2_linear()` never appears in the input.
+    let mut cursor = Cursor::new(line, text);
+    cursor.take_while("a lowercase letter", |c| c.is_ascii_lowercase())?;
+    cursor.end()?;
+
+    // Comparing box IDs only makes sense if they all have the same length. The error points at
+    // the first letter too many, or at the end of a box ID that is too short.
+    match *length {
+        Some(length) if length != text.len() => Err(Error::syntax(
+            line,
+            length.min(text.len()) + 1,
+            text,
+            &format!("a box ID of {} letters", length),
+        )),
+        _ => {
+            *length = Some(text.len());
+            Ok(text.to_string())
+        }
+    }
+}
+
+pub struct Dec02;
+
+impl Solution for Dec02 {
+    type Input = Vec<String>;
+    type Output1 = i32;
+    type Output2 = String;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Vec<String>> {
+        let mut length = None;
+
+        records(reader, |line, text| parse_box_id(line, text, &mut length)).collect()
+    }
+
+    fn part1(lines: &Vec<String>) -> Result<i32> {
+        Ok(compute_result_task1(lines))
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<String> {
+        Ok(compute_result_task2_linear(lines))
+    }
+
+    fn part2_implementations() -> Vec<Implementation<Vec<String>, String>> {
+        vec![
+            Implementation::new("linear", Dec02::part2),
+            Implementation::new("naive", |lines| Ok(compute_result_task2_naive(lines))),
+        ]
+    }
+
+    // The checksum only looks at one box ID at a time, while part 2 compares them all.
+    fn streaming_parts() -> Vec<Part> {
+        vec![Part::One]
+    }
+
+    fn stream_part1(reader: &mut dyn BufRead) -> Result<i32> {
+        let mut length = None;
+        let mut checksum = Checksum::new();
+
+        for id in records(reader, |line, text| parse_box_id(line, text, &mut length)) {
+            checksum.add(&id?);
+        }
+
+        Ok(checksum.value())
+    }
+}
+
+// The box IDs have nothing worth drawing.
+impl Visualizer for Dec02 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_results_task1() {
+        let lines = vec![
+            String::from("abcdef"),
+            String::from("bababc"),
+            String::from("abbcde"),
+            String::from("abcccd"),
+            String::from("aabcdd"),
+            String::from("abcdee"),
+            String::from("ababab"),
+        ];
+
+        assert_eq!(12, compute_result_task1(&lines));
+    }
+
+    #[test]
+    fn known_results_task2() {
+        let lines = vec![
+            String::from("abcde"),
+            String::from("fghij"),
+            String::from("klmno"),
+            String::from("pqrst"),
+            String::from("fguij"),
+            String::from("axcye"),
+            String::from("wvxyz"),
+        ];
+
+        assert_eq!("fgij", compute_result_task2_naive(&lines));
+        assert_eq!("fgij", compute_result_task2_linear(&lines));
+    }
+
+    #[test]
+    fn duplicate_box_ids() {
+        let implementations = Dec02::part2_implementations();
+        let check = |input: &[&str], expected: &str| {
+            let lines: Vec<String> = input.iter().map(|line| line.to_string()).collect();
+
+            for implementation in implementations.iter() {
+                assert_eq!(
+                    implementation.run(&lines).unwrap(),
+                    expected,
+                    "{}",
+                    implementation.name()
+                );
+            }
+        };
+
+        check(&["abcde", "abcde", "fghij"], "Not found");
+        check(&["abcde", "abcde", "abcdf"], "abcd");
+    }
+
+    #[test]
+    fn check_strings_of_other_lengths() {
+        assert_eq!(check_strings("abcde", "abcdf"), Some(String::from("abcd")));
+        assert_eq!(check_strings("abcde", "abcd"), None);
+        assert_eq!(check_strings("", ""), None);
+    }
+
+    #[test]
+    fn parse_rejects_other_lengths() {
+        for (input, column) in [("abc\nabcd\n", 4), ("abcd\nab\n", 3)].iter() {
+            match Dec02::parse(&mut input.as_bytes()) {
+                Err(Error::Parse {
+                    line, column: c, ..
+                }) => {
+                    assert_eq!((line, c), (2, Some(*column)))
+                }
+                _ => panic!("expected a syntax error"),
+            }
+        }
+    }
+
+    #[test]
+    fn stream_part1_matches_part1() {
+        let input = "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n";
+
+        assert_eq!(Dec02::stream_part1(&mut input.as_bytes()).unwrap(), 12);
+        assert!(Dec02::stream_part1(&mut "abc\nabcd\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::metadata::{Answer, Metadata, Puzzle};
+
+use Dec02;
+
+impl Metadata for Dec02 {
+    fn puzzle() -> Puzzle {
+        Puzzle {
+            year: 2018,
+            day: 2,
+            title: "Inventory Management System",
+            parts: [
+                ("the checksum of the box IDs", Answer::Integer),
+                (
+                    "the letters common to the two correct box IDs",
+                    Answer::Text,
+                ),
+            ],
+            input: "one box ID of lowercase letters per line",
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::cache::{Cached, Decoder, Encoder};
+use common::Result;
+
+use {Dec03, Grid};
+
+impl Cached for Dec03 {
+    const VERSION: u32 = 1;
+
+    fn encode(grid: &Grid, encoder: &mut Encoder) {
+        grid.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Grid> {
+        Grid::decode(decoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use common::cache::{self, Cache};
+    use common::{normalize, Solution};
+
+    #[test]
+    fn decoded_grid_matches() {
+        let data = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+        let grid = Dec03::parse(&mut data.as_bytes()).unwrap();
+
+        let mut encoder = Encoder::new();
+        Dec03::encode(&grid, &mut encoder);
+
+        let mut decoder = Decoder::new(encoder.bytes());
+        let decoded = Dec03::decode(&mut decoder).unwrap();
+        assert!(decoder.end().is_ok());
+
+        assert_eq!(decoded.count(2), grid.count(2));
+        assert_eq!(decoded.intact(), grid.intact());
+        assert_eq!(
+            Dec03::part1(&decoded).unwrap(),
+            Dec03::part1(&grid).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_without_a_writable_cache() {
+        // The cache directory cannot be created below a file.
+        let file = env::temp_dir().join(format!("aoc-cache-file-{}", process::id()));
+        fs::write(&file, b"").unwrap();
+
+        let data = b"#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+        let cache = Cache::new(file.join("cache"));
+        let (grid, warnings) = normalize::capture(|| cache::parse::<Dec03>(&cache, "dec03", data));
+
+        assert_eq!(grid.unwrap().intact(), vec![3]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("cannot save the parsed input in"));
+
+        fs::remove_file(&file).unwrap();
+    }
+}
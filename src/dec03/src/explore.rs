@@ -0,0 +1,80 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::explore::{self, Explorer};
+use common::Result;
+
+use {Dec03, Grid, Point};
+
+impl Explorer for Dec03 {
+    fn commands() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "count [LEVEL]",
+                "number of square inches covered by at least LEVEL claims (default 2)",
+            ),
+            (
+                "intact",
+                "the claims that do not overlap with any other claim",
+            ),
+            (
+                "at X Y",
+                "number of claims covering the given square inch and the most recent of them",
+            ),
+        ]
+    }
+
+    fn execute(grid: &Grid, command: &str, args: &[&str]) -> Result<String> {
+        match command {
+            "count" => {
+                let level = explore::optional(args, 0, "LEVEL", 2)?;
+
+                Ok(grid.count(level).to_string())
+            }
+            "intact" => {
+                let mut intact = grid.intact();
+                intact.sort();
+
+                let ids: Vec<String> = intact.iter().map(|id| format!("#{}", id)).collect();
+
+                Ok(ids.join(" "))
+            }
+            "at" => {
+                let point = Point::new(
+                    explore::argument(args, 0, "X")?,
+                    explore::argument(args, 1, "Y")?,
+                );
+
+                match grid.visitor(&point) {
+                    Some(id) => Ok(format!("level={} claim=#{}", grid.level(&point), id)),
+                    None => Ok("level=0".to_string()),
+                }
+            }
+            _ => Err(explore::unknown(command)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::Solution;
+
+    #[test]
+    fn explore_grid() {
+        let data = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+        let grid = Dec03::parse(&mut data.as_bytes()).unwrap();
+
+        assert_eq!(Dec03::execute(&grid, "count", &[]).unwrap(), "4");
+        assert_eq!(Dec03::execute(&grid, "count", &["1"]).unwrap(), "32");
+        assert_eq!(Dec03::execute(&grid, "intact", &[]).unwrap(), "#3");
+        assert_eq!(
+            Dec03::execute(&grid, "at", &["3", "3"]).unwrap(),
+            "level=2 claim=#2"
+        );
+        assert_eq!(Dec03::execute(&grid, "at", &["0", "0"]).unwrap(), "level=0");
+        assert!(Dec03::execute(&grid, "at", &["0"]).is_err());
+    }
+}
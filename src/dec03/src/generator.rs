@@ -0,0 +1,228 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::io::Write;
+
+use common::generate::{Generator, Knobs, Rng};
+use common::{Error, Result};
+
+use parsers::{MAX_POSITION, MAX_SIZE};
+use Dec03;
+
+// Number of attempts at placing a claim outside of the intact claim.
+const ATTEMPTS: usize = 1000;
+
+// The largest fabric the generator handles. `Coverage` keeps a byte for every square inch, so this
+// is well below the largest position the parser accepts.
+const MAX_FABRIC: usize = 5000;
+
+#[derive(Clone, Copy)]
+struct Rectangle {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Rectangle {
+    fn overlaps(&self, other: &Rectangle) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+// A random position in `0..=fabric - size` that overlaps the range `start..start + length`.
+fn overlapping(rng: &mut Rng, fabric: usize, size: usize, start: usize, length: usize) -> usize {
+    let low = (start + 1).saturating_sub(size);
+    let high = (fabric - size).min(start + length - 1);
+
+    rng.range(low as i64, high as i64) as usize
+}
+
+// Keeps track of how many claims cover each square inch of the fabric, saturating at 2.
+struct Coverage {
+    fabric: usize,
+    levels: Vec<u8>,
+}
+
+impl Coverage {
+    fn new(fabric: usize) -> Coverage {
+        Coverage {
+            fabric,
+            levels: vec![0; fabric * fabric],
+        }
+    }
+
+    fn add(&mut self, r: &Rectangle) {
+        for y in r.y..r.y + r.height {
+            for level in &mut self.levels[y * self.fabric + r.x..y * self.fabric + r.x + r.width] {
+                *level = (*level + 1).min(2);
+            }
+        }
+    }
+
+    fn intact(&self, r: &Rectangle) -> bool {
+        (r.y..r.y + r.height).all(|y| {
+            self.levels[y * self.fabric + r.x..y * self.fabric + r.x + r.width]
+                .iter()
+                .all(|level| *level == 1)
+        })
+    }
+}
+
+impl Generator for Dec03 {
+    fn knobs() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("size", "number of claims (default 1000)"),
+            ("fabric", "width and height of the fabric (default 1000)"),
+            ("max", "largest width or height of a claim (default 30)"),
+            (
+                "overlap",
+                "probability that a claim is placed on top of the previous one (default 0.5)",
+            ),
+        ]
+    }
+
+    fn generate(rng: &mut Rng, knobs: &Knobs, writer: &mut dyn Write) -> Result<()> {
+        let size = knobs.get("size", 1000usize)?;
+        let fabric = knobs.get("fabric", 1000usize)?;
+        let max = knobs.get("max", 30usize)?;
+        let overlap = knobs.probability("overlap", 0.5)?;
+
+        if size == 0 || size == 2 || max == 0 || max > fabric {
+            return Err(Error::invalid(
+                "size must be 1 or at least 3 and max between 1 and the fabric size",
+            ));
+        }
+
+        // The claims have to fit the limits of the parser, and the fabric the limit of `Coverage`.
+        if fabric > MAX_FABRIC.min(MAX_POSITION as usize) || max > MAX_SIZE as usize {
+            return Err(Error::invalid(format!(
+                "fabric must be at most {} and max at most {}",
+                MAX_FABRIC.min(MAX_POSITION as usize),
+                MAX_SIZE
+            )));
+        }
+
+        let random = |rng: &mut Rng| {
+            let width = rng.range(1, max as i64) as usize;
+            let height = rng.range(1, max as i64) as usize;
+
+            Rectangle {
+                x: rng.below((fabric - width + 1) as u64) as usize,
+                y: rng.below((fabric - height + 1) as u64) as usize,
+                width,
+                height,
+            }
+        };
+
+        // Part 2 expects exactly one intact claim. Every other claim is kept clear of it and is
+        // made to overlap with at least one other claim below.
+        let intact = rng.below(size as u64) as usize;
+        let reserved = random(rng);
+
+        let mut claims: Vec<Rectangle> = Vec::with_capacity(size);
+
+        for index in 0..size {
+            if index == intact {
+                claims.push(reserved);
+                continue;
+            }
+
+            let previous = claims
+                .iter()
+                .rev()
+                .find(|c| !c.overlaps(&reserved))
+                .cloned();
+
+            let claim = (0..ATTEMPTS)
+                .map(|_| {
+                    let mut claim = random(rng);
+
+                    if let Some(previous) = previous {
+                        if rng.chance(overlap) {
+                            let (w, h) = (claim.width, claim.height);
+                            claim.x = overlapping(rng, fabric, w, previous.x, previous.width);
+                            claim.y = overlapping(rng, fabric, h, previous.y, previous.height);
+                        }
+                    }
+
+                    claim
+                })
+                .find(|claim| !claim.overlaps(&reserved))
+                .ok_or_else(|| Error::invalid("the fabric is too small for the claims"))?;
+
+            claims.push(claim);
+        }
+
+        let mut coverage = Coverage::new(fabric);
+
+        for claim in claims.iter() {
+            coverage.add(claim);
+        }
+
+        // Every other claim that ended up intact is moved on top of another claim.
+        for index in 0..size {
+            if index == intact || !coverage.intact(&claims[index]) {
+                continue;
+            }
+
+            let other = (0..size)
+                .find(|other| *other != index && *other != intact)
+                .unwrap();
+
+            claims[index] = claims[other];
+            coverage.add(&claims[index]);
+        }
+
+        for (index, claim) in claims.iter().enumerate() {
+            writeln!(
+                writer,
+                "#{} @ {},{}: {}x{}",
+                index + 1,
+                claim.x,
+                claim.y,
+                claim.width,
+                claim.height
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::generate;
+    use common::Solution;
+
+    #[test]
+    fn generated_input_parses() {
+        for overlap in ["0", "0.5", "1"].iter() {
+            let knobs = Knobs::parse(&[
+                "size=200".to_string(),
+                "fabric=100".to_string(),
+                "max=10".to_string(),
+                format!("overlap={}", overlap),
+            ])
+            .unwrap();
+            let data = generate::generate::<Dec03>(7, &knobs).unwrap();
+
+            assert_eq!(data, generate::generate::<Dec03>(7, &knobs).unwrap());
+
+            let grid = Dec03::parse(&mut &data[..]).unwrap();
+            assert!(Dec03::part2(&grid).is_ok());
+        }
+    }
+
+    #[test]
+    fn fabric_is_limited() {
+        let knobs = Knobs::parse(&["fabric=100000"]).unwrap();
+        assert!(generate::generate::<Dec03>(7, &knobs).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+mod cache;
+mod explore;
+mod generator;
+mod metadata;
+pub mod parsers;
+pub mod types;
+mod visualize;
+
+#[macro_use]
+extern crate common;
+
+use std::io::BufRead;
+
+use common::input::records;
+use common::{Error, Result, Solution};
+pub use parsers::parse_claim;
+pub use types::{Claim, ClaimID, Dimension, Grid, Point};
+
+pub fn compute_result_task1(grid: &Grid) -> usize {
+    let count = grid.count(2);
+    trace!("overlapping square inches", "count" => count, "claimed" => grid.count(1));
+
+    count
+}
+
+pub fn compute_result_task2(grid: &Grid) -> Result<i32> {
+    let v = grid.intact();
+
+    if v.len() != 1 {
+        return Err(Error::invalid(format!(
+            "expected exactly one intact claim, found {}",
+            v.len()
+        )));
+    }
+
+    trace!("intact claim", "id" => v[0]);
+
+    Ok(v[0])
+}
+
+pub struct Dec03;
+
+impl Solution for Dec03 {
+    type Input = Grid;
+    type Output1 = usize;
+    type Output2 = i32;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Grid> {
+        let mut grid = Grid::new();
+        let mut last_id = 0;
+
+        // The grid expects the claims to arrive in increasing order of their ID's, so the ID of
+        // each claim is checked while we still have its line.
+        let claims = records(reader, |line, text| {
+            let claim = parsers::parse_claim(line, text)?;
+
+            if claim.id() <= last_id {
+                let column = text.find(|c: char| c.is_ascii_digit()).unwrap_or(0) + 1;
+
+                return Err(Error::syntax(
+                    line,
+                    column,
+                    text,
+                    &format!("a claim ID above {}", last_id),
+                ));
+            }
+
+            last_id = claim.id();
+            Ok(claim)
+        });
+
+        for claim in claims {
+            grid.update(&claim?);
+        }
+
+        Ok(grid)
+    }
+
+    fn part1(grid: &Grid) -> Result<usize> {
+        Ok(compute_result_task1(grid))
+    }
+
+    fn part2(grid: &Grid) -> Result<i32> {
+        compute_result_task2(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_results_task1() {
+        // Sample claims.
+        let claims = vec![
+            parsers::parse_claim(1, "#1 @ 1,3: 4x4").unwrap(),
+            parsers::parse_claim(1, "#2 @ 3,1: 4x4").unwrap(),
+            parsers::parse_claim(1, "#3 @ 5,5: 2x2").unwrap(),
+        ];
+
+        // Our grid.
+        let mut grid = Grid::new();
+
+        for claim in claims {
+            grid.update(&claim);
+        }
+
+        assert_eq!(grid.count(2), 4);
+    }
+
+    #[test]
+    fn known_results_task2() {
+        // Sample claims.
+        let claims = vec![
+            parsers::parse_claim(1, "#1 @ 1,3: 4x4").unwrap(),
+            parsers::parse_claim(1, "#2 @ 3,1: 4x4").unwrap(),
+            parsers::parse_claim(1, "#3 @ 5,5: 2x2").unwrap(),
+        ];
+
+        // Our grid.
+        let mut grid = Grid::new();
+
+        for claim in claims {
+            grid.update(&claim);
+        }
+
+        let v = grid.intact();
+
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0], 3);
+    }
+
+    #[test]
+    fn parse_claim_known_values() {
+        let a = parsers::parse_claim(1, "#1 @ 1,3: 4x4").unwrap();
+        assert_eq!(a.id(), 1);
+        let a_points = a.points();
+        assert_eq!(a_points.len(), 16);
+
+        let b = parsers::parse_claim(1, "#2 @ 3,1: 4x4").unwrap();
+        assert_eq!(b.id(), 2);
+        let b_points = b.points();
+        assert_eq!(b_points.len(), 16);
+
+        let c = parsers::parse_claim(1, "#3 @ 5,5: 2x2").unwrap();
+        assert_eq!(c.id(), 3);
+        let c_points = c.points();
+        assert_eq!(c_points.len(), 4);
+    }
+
+    #[test]
+    fn parse_claim_reports_column() {
+        match parsers::parse_claim(7, "#1 @ 1,3 4x4") {
+            Err(Error::Parse { line, column, .. }) => {
+                assert_eq!(line, 7);
+                assert_eq!(column, Some(10));
+            }
+            _ => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_unordered_claims() {
+        let input = "#1 @ 1,3: 4x4\n#3 @ 3,1: 4x4\n#2 @ 5,5: 2x2\n";
+
+        match Dec03::parse(&mut input.as_bytes()) {
+            Err(Error::Parse {
+                line,
+                column,
+                message,
+                ..
+            }) => {
+                assert_eq!((line, column), (3, Some(2)));
+                assert_eq!(message, "expected a claim ID above 3");
+            }
+            _ => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    fn parse_claim_limits() {
+        assert!(parsers::parse_claim(1, "#1 @ 100000,3: 1000x1").is_ok());
+
+        match parsers::parse_claim(1, "#1 @ 1,3: 4x1001") {
+            Err(Error::Parse { column, .. }) => assert_eq!(column, Some(13)),
+            _ => panic!("expected a syntax error"),
+        }
+
+        assert!(parsers::parse_claim(1, "#1 @ 2147483647,3: 4x4").is_err());
+        assert!(parsers::parse_claim(1, "#1 @ 1,3: 0x4").is_err());
+    }
+}
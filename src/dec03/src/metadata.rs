@@ -0,0 +1,28 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::metadata::{Answer, Metadata, Puzzle};
+
+use Dec03;
+
+impl Metadata for Dec03 {
+    fn puzzle() -> Puzzle {
+        Puzzle {
+            year: 2018,
+            day: 3,
+            title: "No Matter How You Slice It",
+            parts: [
+                (
+                    "the square inches of fabric within two or more claims",
+                    Answer::Integer,
+                ),
+                (
+                    "the ID of the only claim that does not overlap",
+                    Answer::Integer,
+                ),
+            ],
+            input: "one claim per line with its ID, position and size, such as #1 @ 1,3: 4x4",
+        }
+    }
+}
@@ -2,38 +2,44 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use nom;
-use nom::types::CompleteStr;
+use common::parse::Cursor;
+use common::Result;
 
 use types::{Claim, Dimension, Point};
 
-named!(integer<CompleteStr, i32>,
-    flat_map!(nom::digit, parse_to!(i32)));
-
-named!(point<CompleteStr, Point>,
-    do_parse!(x: integer >>
-              tag_s!(",")  >>
-              y: integer >>
-              (Point::new(x, y))));
-
-named!(dimension<CompleteStr, Dimension>,
-    do_parse!(width: integer  >>
-              tag!("x")       >>
-              height: integer >>
-              (Dimension::new(width, height))));
-
-named!(claim<CompleteStr, Claim>,
-    do_parse!(tag!("#")            >>
-              id: integer          >>
-              ws!(tag!("@"))       >>
-              point: point         >>
-              ws!(tag!(":"))       >>
-              dimension: dimension >>
-              (Claim::new(id, point, dimension))));
-
-pub fn parse_claim(s: &str) -> Option<Claim> {
-    match claim(CompleteStr(s)) {
-        Ok((CompleteStr(""), claim)) => Some(claim),
-        _ => None,
-    }
+// The puzzle uses a fabric of at least 1000 inches on each side with far smaller claims. These
+// limits keep a single line from claiming more square inches than we can keep track of, and the
+// edges of a claim from overflowing.
+pub const MAX_POSITION: u32 = 100_000;
+pub const MAX_SIZE: u32 = 1_000;
+
+fn point(cursor: &mut Cursor) -> Result<Point> {
+    let x = cursor.bounded("position", 0, MAX_POSITION)?;
+    cursor.tag(",")?;
+    let y = cursor.bounded("position", 0, MAX_POSITION)?;
+
+    Ok(Point::new(x as i32, y as i32))
+}
+
+fn dimension(cursor: &mut Cursor) -> Result<Dimension> {
+    let width = cursor.bounded("width", 1, MAX_SIZE)?;
+    cursor.tag("x")?;
+    let height = cursor.bounded("height", 1, MAX_SIZE)?;
+
+    Ok(Dimension::new(width as i32, height as i32))
+}
+
+// Parse a claim such as "#1 @ 1,3: 4x4". The line number is only used for errors.
+pub fn parse_claim(line: usize, s: &str) -> Result<Claim> {
+    let mut cursor = Cursor::new(line, s);
+
+    cursor.tag("#")?;
+    let id = cursor.unsigned()?;
+    cursor.separator("@")?;
+    let point = point(&mut cursor)?;
+    cursor.separator(":")?;
+    let dimension = dimension(&mut cursor)?;
+    cursor.end()?;
+
+    Ok(Claim::new(id, point, dimension))
 }
@@ -6,6 +6,9 @@ use std::cmp::{max, min};
 use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
+use common::cache::{Decoder, Encoder};
+use common::Result;
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Point {
     x: i32,
@@ -46,7 +49,7 @@ impl Dimension {
     }
 }
 
-type ClaimID = i32;
+pub type ClaimID = i32;
 
 #[derive(Debug)]
 pub struct Claim {
@@ -64,7 +67,6 @@ impl Claim {
         }
     }
 
-    #[allow(dead_code)]
     pub fn id(&self) -> ClaimID {
         self.id
     }
@@ -165,11 +167,72 @@ impl Grid {
         self.grid.values().filter(|l| **l >= min_level).count()
     }
 
+    // The number of claims covering the given point.
+    pub fn level(&self, point: &Point) -> usize {
+        self.grid.get(point).cloned().unwrap_or(0)
+    }
+
+    // The most recent claim covering the given point, if any.
+    pub fn visitor(&self, point: &Point) -> Option<ClaimID> {
+        self.recent_visitor.get(point).cloned()
+    }
+
+    // Every claimed point and the number of claims covering it, in no particular order.
+    pub fn levels(&self) -> impl Iterator<Item = (&Point, usize)> {
+        self.grid.iter().map(|(point, level)| (point, *level))
+    }
+
     pub fn intact(&self) -> Vec<ClaimID> {
         Vec::from_iter(self.intact.iter().cloned())
     }
 
-    #[allow(dead_code)]
+    // Save the grid for the cache of parsed inputs.
+    pub fn encode(&self, encoder: &mut Encoder) {
+        encoder.usize(self.grid.len());
+
+        for (point, level) in self.grid.iter() {
+            encoder.i32(point.x);
+            encoder.i32(point.y);
+            encoder.usize(*level);
+            encoder.i32(self.recent_visitor[point]);
+        }
+
+        encoder.usize(self.intact.len());
+
+        for id in self.intact.iter() {
+            encoder.i32(*id);
+        }
+
+        for value in [self.last_id, self.min_x, self.max_x, self.min_y, self.max_y].iter() {
+            encoder.i32(*value);
+        }
+    }
+
+    // Load a grid saved by `encode()`.
+    pub fn decode(decoder: &mut Decoder) -> Result<Grid> {
+        let mut grid = Grid::new();
+
+        for _ in 0..decoder.usize()? {
+            let point = Point::new(decoder.i32()?, decoder.i32()?);
+            let level = decoder.usize()?;
+
+            grid.recent_visitor.insert(point.clone(), decoder.i32()?);
+            grid.grid.insert(point, level);
+        }
+
+        for _ in 0..decoder.usize()? {
+            grid.intact.insert(decoder.i32()?);
+        }
+
+        grid.last_id = decoder.i32()?;
+        grid.min_x = decoder.i32()?;
+        grid.max_x = decoder.i32()?;
+        grid.min_y = decoder.i32()?;
+        grid.max_y = decoder.i32()?;
+
+        Ok(grid)
+    }
+
     pub fn print(&self) {
         for x in self.min_x..(self.max_x + 2) {
             for y in self.min_y..(self.max_y + 2) {
@@ -179,7 +242,13 @@ impl Grid {
                 }
             }
 
-            println!("");
+            println!();
         }
     }
 }
+
+impl Default for Grid {
+    fn default() -> Grid {
+        Grid::new()
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::cmp::max;
+use std::collections::BTreeMap;
+
+use common::visualize::{heat, Figure, Svg, Visualizer};
+
+use {Dec03, Grid};
+
+// The fabric is drawn as at most this many cells across, each covering a square of several square
+// inches if the fabric is larger, such that the figure stays small.
+const CELLS: i32 = 100;
+
+// The size of a cell in pixels.
+const CELL_SIZE: f64 = 5.0;
+
+// Draw how many claims cover each part of the fabric, with the intact claim outlined.
+fn heat_map(grid: &Grid) -> String {
+    let extent = grid
+        .levels()
+        .map(|(point, _)| max(point.x(), point.y()) + 1)
+        .max()
+        .unwrap_or(1);
+    let scale = (extent + CELLS - 1) / CELLS;
+    let size = f64::from((extent + scale - 1) / scale) * CELL_SIZE;
+
+    // The highest level within each cell and the bounds of the intact claims, in cells.
+    let mut cells: BTreeMap<(i32, i32), usize> = BTreeMap::new();
+    let mut intact: Option<(i32, i32, i32, i32)> = None;
+    let ids = grid.intact();
+
+    for (point, level) in grid.levels() {
+        let (x, y) = (point.x() / scale, point.y() / scale);
+        let cell = cells.entry((x, y)).or_insert(0);
+        *cell = max(*cell, level);
+
+        if level == 1 && grid.visitor(point).is_some_and(|id| ids.contains(&id)) {
+            intact = Some(match intact {
+                None => (x, y, x, y),
+                Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+            });
+        }
+    }
+
+    let highest = cells.values().cloned().max().unwrap_or(1);
+    let mut svg = Svg::new(size, size);
+    svg.rect(0.0, 0.0, size, size, "#f4f4f4", "");
+
+    for (&(x, y), &level) in cells.iter() {
+        let value = if highest > 1 {
+            (level - 1) as f64 / (highest - 1) as f64
+        } else {
+            0.0
+        };
+        let title = if scale == 1 {
+            format!("{},{}: {} claim(s)", x, y, level)
+        } else {
+            format!(
+                "{},{} to {},{}: up to {} claim(s)",
+                x * scale,
+                y * scale,
+                (x + 1) * scale - 1,
+                (y + 1) * scale - 1,
+                level
+            )
+        };
+
+        svg.rect(
+            f64::from(x) * CELL_SIZE,
+            f64::from(y) * CELL_SIZE,
+            CELL_SIZE,
+            CELL_SIZE,
+            &heat(value),
+            &title,
+        );
+    }
+
+    if let Some((x0, y0, x1, y1)) = intact {
+        svg.frame(
+            f64::from(x0) * CELL_SIZE,
+            f64::from(y0) * CELL_SIZE,
+            f64::from(x1 - x0 + 1) * CELL_SIZE,
+            f64::from(y1 - y0 + 1) * CELL_SIZE,
+            "#2166ac",
+        );
+    }
+
+    svg.finish()
+}
+
+impl Visualizer for Dec03 {
+    fn figures(grid: &Grid) -> Vec<Figure> {
+        vec![Figure {
+            title: String::from("Claims covering the fabric, with the intact claim outlined"),
+            svg: heat_map(grid),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::Solution;
+
+    #[test]
+    fn draw_heat_map() {
+        let data = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+        let grid = Dec03::parse(&mut data.as_bytes()).unwrap();
+        let svg = heat_map(&grid);
+
+        // Every claimed square inch is drawn, and the overlap in the darkest colour.
+        assert_eq!(svg.matches("claim(s)</title>").count(), 32);
+        assert!(svg.contains("fill=\"#7f0000\"><title>3,3: 2 claim(s)</title>"));
+        assert!(svg.contains(
+            "<rect x=\"25\" y=\"25\" width=\"10\" height=\"10\" fill=\"none\" stroke=\"#2166ac\""
+        ));
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::cache::{Cached, Decoder, Encoder};
+use common::Result;
+
+use {Dec04, EventTracker};
+
+impl Cached for Dec04 {
+    const VERSION: u32 = 1;
+
+    fn encode(tracker: &EventTracker, encoder: &mut Encoder) {
+        tracker.encode(encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<EventTracker> {
+        EventTracker::decode(decoder)
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::explore::{self, Explorer};
+use common::{Error, Result};
+
+use {format_histogram, Dec04, EventTracker, GuardID, GuardSummary};
+
+fn format_summary(summary: &GuardSummary) -> String {
+    format!(
+        "#{} asleep={} minute={} times={}",
+        summary.id(),
+        summary.minutes_asleep(),
+        summary.most_missed_timestamp().minutes(),
+        summary.most_missed_timestamp_count()
+    )
+}
+
+impl Explorer for Dec04 {
+    fn commands() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "guards",
+                "every guard with the minutes asleep and the sleepiest minute",
+            ),
+            (
+                "guard ID",
+                "the given guard and how often it slept during each minute",
+            ),
+        ]
+    }
+
+    fn execute(tracker: &EventTracker, command: &str, args: &[&str]) -> Result<String> {
+        let mut summaries = tracker.summaries();
+        summaries.sort_by_key(|summary| summary.id());
+
+        match command {
+            "guards" => {
+                let lines: Vec<String> = summaries.iter().map(format_summary).collect();
+
+                Ok(lines.join("\n"))
+            }
+            "guard" => {
+                let id: GuardID = explore::argument(args, 0, "ID")?;
+
+                let summary = summaries
+                    .iter()
+                    .find(|summary| summary.id() == id)
+                    .ok_or_else(|| Error::invalid(format!("there is no guard #{}", id)))?;
+
+                Ok(format!(
+                    "{} histogram={}",
+                    format_summary(summary),
+                    format_histogram(&tracker.histogram(id))
+                ))
+            }
+            _ => Err(explore::unknown(command)),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::io::Write;
+
+use common::generate::{Generator, Knobs, Rng};
+use common::{Error, Result};
+
+use Dec04;
+
+fn leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// The day after the given (year, month, day).
+fn next_day((year, month, day): (i32, u32, u32)) -> (i32, u32, u32) {
+    if day < days_in_month(year, month) {
+        (year, month, day + 1)
+    } else if month < 12 {
+        (year, month + 1, 1)
+    } else {
+        (year + 1, 1, 1)
+    }
+}
+
+fn timestamp((year, month, day): (i32, u32, u32), hour: u32, minute: u32) -> String {
+    format!(
+        "[{:04}-{:02}-{:02} {:02}:{:02}]",
+        year, month, day, hour, minute
+    )
+}
+
+impl Generator for Dec04 {
+    fn knobs() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("days", "number of shifts, one per day (default 365)"),
+            ("guards", "number of distinct guards (default 20)"),
+            ("naps", "largest number of naps during a shift (default 3)"),
+            ("year", "year of the first shift (default 1518)"),
+            (
+                "shuffle",
+                "whether the records are shuffled like the real input (default true)",
+            ),
+        ]
+    }
+
+    fn generate(rng: &mut Rng, knobs: &Knobs, writer: &mut dyn Write) -> Result<()> {
+        let days = knobs.get("days", 365usize)?;
+        let guards = knobs.get("guards", 20u64)?;
+        let naps = knobs.get("naps", 3u64)?;
+        let year = knobs.get("year", 1518i32)?;
+        let shuffle = knobs.get("shuffle", true)?;
+
+        if guards == 0 || naps > 27 || !(1..=9998).contains(&year) {
+            return Err(Error::invalid(
+                "guards must be positive, naps at most 27 and year between 1 and 9998",
+            ));
+        }
+
+        // Pick distinct guard IDs.
+        let mut ids: Vec<u64> = Vec::new();
+
+        while (ids.len() as u64) < guards {
+            let id = rng.range(1, (guards * 100) as i64) as u64;
+
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        let mut records = Vec::new();
+        let mut yesterday = (year - 1, 12, 31);
+
+        for _ in 0..days {
+            let today = next_day(yesterday);
+            let guard = ids[rng.below(guards) as usize];
+
+            // The guard either arrives a bit before midnight or within the first minutes of the
+            // midnight hour. They can only fall asleep after they have arrived.
+            let first = if rng.chance(0.5) {
+                let minute = rng.range(45, 59) as u32;
+                records.push(format!(
+                    "{} Guard #{} begins shift",
+                    timestamp(yesterday, 23, minute),
+                    guard
+                ));
+                0
+            } else {
+                let minute = rng.range(0, 5) as u32;
+                records.push(format!(
+                    "{} Guard #{} begins shift",
+                    timestamp(today, 0, minute),
+                    guard
+                ));
+                minute + 1
+            };
+
+            // Pick an even number of distinct minutes to fall asleep and wake up at, alternately.
+            let count = 2 * rng.below(naps + 1) as usize;
+            let mut minutes: Vec<u32> = (first..60).collect();
+            rng.shuffle(&mut minutes);
+            minutes.truncate(count);
+            minutes.sort();
+
+            for (index, minute) in minutes.iter().enumerate() {
+                let event = if index % 2 == 0 {
+                    "falls asleep"
+                } else {
+                    "wakes up"
+                };
+
+                records.push(format!("{} {}", timestamp(today, 0, *minute), event));
+            }
+
+            yesterday = today;
+        }
+
+        if shuffle {
+            rng.shuffle(&mut records);
+        }
+
+        for record in records {
+            writeln!(writer, "{}", record)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::generate;
+    use common::Solution;
+
+    #[test]
+    fn next_days() {
+        assert_eq!(next_day((1518, 2, 28)), (1518, 3, 1));
+        assert_eq!(next_day((1600, 2, 28)), (1600, 2, 29));
+        assert_eq!(next_day((1518, 12, 31)), (1519, 1, 1));
+    }
+
+    #[test]
+    fn generated_input_parses() {
+        let knobs = Knobs::parse(&["days=1000", "guards=10", "naps=5"]).unwrap();
+        let data = generate::generate::<Dec04>(7, &knobs).unwrap();
+
+        assert_eq!(data, generate::generate::<Dec04>(7, &knobs).unwrap());
+
+        let tracker = Dec04::parse(&mut &data[..]).unwrap();
+        assert!(tracker.summaries().len() <= 10);
+        assert!(Dec04::part1(&tracker).is_ok());
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+mod cache;
+mod explore;
+mod generator;
+mod metadata;
+pub mod parsers;
+pub mod types;
+mod visualize;
+
+#[macro_use]
+extern crate common;
+
+use std::io::BufRead;
+
+use common::input::records;
+use common::{Error, Result, Solution};
+pub use parsers::parse;
+pub use types::{
+    Date, DateTime, Event, EventTracker, EventType, GuardID, GuardSummary, SleepTracker, Time,
+};
+
+// The non-zero entries of a minute histogram as "minute:count" pairs.
+fn format_histogram(histogram: &[usize]) -> String {
+    let entries: Vec<String> = histogram
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .map(|(minute, count)| format!("{}:{}", minute, count))
+        .collect();
+
+    entries.join(",")
+}
+
+pub fn compute_result_task1(tracker: &EventTracker) -> usize {
+    let summaries = tracker.summaries();
+    let mut guard_id = 0;
+    let mut minutes_asleep = 0;
+    let mut minute = 0;
+
+    for summary in summaries {
+        if summary.minutes_asleep() > minutes_asleep {
+            guard_id = summary.id();
+            minute = summary.most_missed_timestamp().minutes();
+            minutes_asleep = summary.minutes_asleep();
+        }
+    }
+
+    trace!(
+        "sleepiest guard",
+        "guard" => guard_id,
+        "minutes_asleep" => minutes_asleep,
+        "minute" => minute,
+        "histogram" => format_histogram(&tracker.histogram(guard_id))
+    );
+
+    guard_id as usize * minute as usize
+}
+
+pub fn compute_result_task2(tracker: &EventTracker) -> usize {
+    let summaries = tracker.summaries();
+    let mut guard_id = 0;
+    let mut max_count = 0;
+    let mut minute = 0;
+
+    for summary in summaries {
+        if summary.most_missed_timestamp_count() > max_count {
+            guard_id = summary.id();
+            minute = summary.most_missed_timestamp().minutes();
+            max_count = summary.most_missed_timestamp_count();
+        }
+    }
+
+    trace!(
+        "most regular guard",
+        "guard" => guard_id,
+        "minute" => minute,
+        "times_asleep" => max_count,
+        "histogram" => format_histogram(&tracker.histogram(guard_id))
+    );
+
+    guard_id as usize * minute as usize
+}
+
+// The column of the event type of a line that parsed, such as "wakes up" in
+// "[1518-11-01 00:25] wakes up".
+fn event_column(text: &str) -> usize {
+    let start = text.find(']').map_or(0, |index| index + 1);
+    let rest = &text[start..];
+
+    start + rest.len() - rest.trim_start().len() + 1
+}
+
+pub struct Dec04;
+
+impl Solution for Dec04 {
+    type Input = EventTracker;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<EventTracker> {
+        let mut tracker = EventTracker::new();
+        let mut events = Vec::new();
+
+        let lines = records(reader, |line, text| {
+            parsers::parse(line, text).map(|event| (event, line, text.to_string()))
+        });
+
+        for event in lines {
+            events.push(event?);
+        }
+
+        // Sort the events chronologically, but keep track of the line each event came from such
+        // that we can point to its event type if it turns out to be invalid.
+        events.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (event, line, text) in events.iter() {
+            tracker
+                .event(event)
+                .map_err(|expected| Error::syntax(*line, event_column(text), text, expected))?;
+        }
+
+        Ok(tracker)
+    }
+
+    fn part1(tracker: &EventTracker) -> Result<usize> {
+        Ok(compute_result_task1(tracker))
+    }
+
+    fn part2(tracker: &EventTracker) -> Result<usize> {
+        Ok(compute_result_task2(tracker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_test_tracker() -> EventTracker {
+        let lines = vec![
+            "[1518-11-01 00:00] Guard #10 begins shift",
+            "[1518-11-01 00:05] falls asleep",
+            "[1518-11-01 00:25] wakes up",
+            "[1518-11-01 00:30] falls asleep",
+            "[1518-11-01 00:55] wakes up",
+            "[1518-11-01 23:58] Guard #99 begins shift",
+            "[1518-11-02 00:40] falls asleep",
+            "[1518-11-02 00:50] wakes up",
+            "[1518-11-03 00:05] Guard #10 begins shift",
+            "[1518-11-03 00:24] falls asleep",
+            "[1518-11-03 00:29] wakes up",
+            "[1518-11-04 00:02] Guard #99 begins shift",
+            "[1518-11-04 00:36] falls asleep",
+            "[1518-11-04 00:46] wakes up",
+            "[1518-11-05 00:03] Guard #99 begins shift",
+            "[1518-11-05 00:45] falls asleep",
+            "[1518-11-05 00:55] wakes up",
+        ];
+
+        let mut tracker = EventTracker::new();
+
+        for (index, line) in lines.iter().enumerate() {
+            tracker
+                .event(&parsers::parse(index + 1, line).unwrap())
+                .unwrap();
+        }
+
+        tracker
+    }
+
+    #[test]
+    fn known_results_task1() {
+        let tracker = build_test_tracker();
+        assert_eq!(10 * 24, compute_result_task1(&tracker));
+    }
+
+    #[test]
+    fn known_results_task2() {
+        let tracker = build_test_tracker();
+        assert_eq!(99 * 45, compute_result_task2(&tracker));
+    }
+
+    #[test]
+    fn trace_sleepiest_guard() {
+        let tracker = build_test_tracker();
+        let (_, events) = common::trace::capture(|| compute_result_task1(&tracker));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].to_string(),
+            "sleepiest guard guard=10 minutes_asleep=50 minute=24 \
+             histogram=5:1,6:1,7:1,8:1,9:1,10:1,11:1,12:1,13:1,14:1,15:1,16:1,17:1,18:1,19:1,\
+             20:1,21:1,22:1,23:1,24:2,25:1,26:1,27:1,28:1,30:1,31:1,32:1,33:1,34:1,35:1,36:1,\
+             37:1,38:1,39:1,40:1,41:1,42:1,43:1,44:1,45:1,46:1,47:1,48:1,49:1,50:1,51:1,52:1,\
+             53:1,54:1"
+        );
+    }
+
+    #[test]
+    fn parse_rejects_inconsistent_events() {
+        let input = "[1518-11-01 00:00] Guard #10 begins shift\n\
+                     [1518-11-01 00:25] wakes up\n";
+
+        match Dec04::parse(&mut input.as_bytes()) {
+            Err(Error::Parse {
+                line,
+                column,
+                message,
+                ..
+            }) => {
+                assert_eq!((line, column), (2, Some(20)));
+                assert_eq!(
+                    message,
+                    "expected the guard to fall asleep before waking up"
+                );
+            }
+            _ => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    fn parse_reports_column() {
+        match parsers::parse(3, "[1518-11-01 00:61] wakes up") {
+            Err(Error::Parse { line, column, .. }) => {
+                assert_eq!(line, 3);
+                assert_eq!(column, Some(16));
+            }
+            _ => panic!("expected a syntax error"),
+        }
+
+        assert!(parsers::parse(1, "[1518-11-01 00:05] falls awake").is_err());
+    }
+}
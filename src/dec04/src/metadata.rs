@@ -0,0 +1,29 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::metadata::{Answer, Metadata, Puzzle};
+
+use Dec04;
+
+impl Metadata for Dec04 {
+    fn puzzle() -> Puzzle {
+        Puzzle {
+            year: 2018,
+            day: 4,
+            title: "Repose Record",
+            parts: [
+                (
+                    "the ID of the guard asleep the most times the minute they sleep the most",
+                    Answer::Integer,
+                ),
+                (
+                    "the ID of the guard most often asleep on the same minute times that minute",
+                    Answer::Integer,
+                ),
+            ],
+            input: "one timestamped record per line in any order, such as \
+                    [1518-11-01 00:00] Guard #10 begins shift, falls asleep or wakes up",
+        }
+    }
+}
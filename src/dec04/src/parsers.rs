@@ -2,94 +2,57 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use nom;
-use nom::types::CompleteStr;
+use common::parse::Cursor;
+use common::Result;
 
-use types::{Date, DateTime, Event, EventType, GuardID, Time};
+use types::{Date, DateTime, Event, EventType, Time};
 
-// Parse an integer literal into a u32.
-named!(integer<CompleteStr, u32>,
-    flat_map!(nom::digit, parse_to!(u32)));
+// Parse a date+time such as "1518-11-01 00:05".
+fn datetime(cursor: &mut Cursor) -> Result<DateTime> {
+    let (year, month, day) = cursor.date()?;
+    cursor.tag(" ")?;
+    let (hours, minutes) = cursor.time()?;
 
-// Parse a Guard ID.
-named!(guard_id<CompleteStr, GuardID>,
-    do_parse!(tag!("#")   >>
-              id: integer >>
-              (id)));
-
-// Parse a year.
-named!(year<CompleteStr, i32>,
-   flat_map!(nom::digit, parse_to!(i32)));
-
-// Parse a month.
-named!(month<CompleteStr, u32>,
-   flat_map!(nom::digit, parse_to!(u32)));
-
-// Parse a day.
-named!(day<CompleteStr, u32>,
-   flat_map!(nom::digit, parse_to!(u32)));
-
-// Parse a date.
-named!(date<CompleteStr, Date>,
-    do_parse!(year: year   >>
-              tag!("-")    >>
-              month: month >>
-              tag!("-")    >>
-              day: day     >>
-              (Date::new(year, month, day))));
-
-// Parse hours.
-named!(hours<CompleteStr, u8>,
-   flat_map!(nom::digit, parse_to!(u8)));
-
-// Parse minutes.
-named!(minutes<CompleteStr, u8>,
-   flat_map!(nom::digit, parse_to!(u8)));
+    Ok(DateTime::new(
+        Date::new(year, month, day),
+        Time::new(hours as u8, minutes as u8),
+    ))
+}
 
-// Parse time.
-named!(time<CompleteStr, Time>,
-    do_parse!(hours: hours     >>
-              tag!(":")        >>
-              minutes: minutes >>
-              (Time::new(hours, minutes))));
+// Parse an event type.
+fn event_type(cursor: &mut Cursor) -> Result<EventType> {
+    if cursor.try_tag("wakes up") {
+        return Ok(EventType::GuardAwake);
+    }
 
-// Parse a date+time.
-named!(datetime<CompleteStr, DateTime>,
-    do_parse!(date: date >>
-              tag!(" ")  >>
-              time: time >>
-              (DateTime::new(date, time))));
+    if cursor.try_tag("falls asleep") {
+        return Ok(EventType::GuardAsleep);
+    }
 
-// Parse a "wakes up" event type.
-named!(wakes_up<CompleteStr, EventType>,
-    do_parse!(tag!("wakes up") >> (EventType::GuardAwake)));
+    if cursor.try_tag("Guard") {
+        cursor.separator("#")?;
+        let id = cursor.unsigned()?;
+        cursor.whitespace();
+        cursor.tag("begins shift")?;
 
-// Parse a "falls asleep" event type.
-named!(falls_asleep<CompleteStr, EventType>,
-    do_parse!(tag!("falls asleep") >> (EventType::GuardAsleep)));
+        return Ok(EventType::GuardBeginsShift(id));
+    }
 
-// Parse a "begins shift" event type.
-named!(begins_shift<CompleteStr, EventType>,
-    do_parse!(tag!("Guard")        >>
-              id: ws!(guard_id)    >>
-              tag!("begins shift") >>
-              (EventType::GuardBeginsShift(id))));
+    Err(cursor.error("\"wakes up\", \"falls asleep\" or \"Guard\""))
+}
 
-// Parse an event type.
-named!(event_type<CompleteStr, EventType>,
-    alt!(wakes_up | falls_asleep | begins_shift));
+// Parse an event such as "[1518-11-01 00:05] falls asleep". The line number is only used for
+// errors.
+pub fn parse(line: usize, s: &str) -> Result<Event> {
+    let mut cursor = Cursor::new(line, s);
 
-// Parse an event.
-named!(event<CompleteStr, Event>,
-    do_parse!(tag!("[")                   >>
-              datetime: datetime          >>
-              tag!("]")                   >>
-              event_type: ws!(event_type) >>
-              (Event::new(datetime, event_type))));
+    cursor.tag("[")?;
+    let datetime = datetime(&mut cursor)?;
+    cursor.tag("]")?;
+    cursor.whitespace();
+    let event_type = event_type(&mut cursor)?;
+    cursor.whitespace();
+    cursor.end()?;
 
-pub fn parse(s: &str) -> Option<Event> {
-    match event(CompleteStr(s)) {
-        Ok((CompleteStr(""), event)) => Some(event),
-        _ => None,
-    }
+    Ok(Event::new(datetime, event_type))
 }
@@ -5,8 +5,11 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+use common::cache::{Decoder, Encoder};
+
 pub type GuardID = u32;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Eq, PartialEq)]
 pub enum EventType {
     // Our guard wakes up.
@@ -195,26 +198,32 @@ impl EventTracker {
         }
     }
 
-    pub fn event(&mut self, event: &Event) {
+    // Feed the next event into the tracker. Events must arrive in chronological order. Returns what
+    // was expected instead if the event does not make sense given the events seen so far.
+    pub fn event(&mut self, event: &Event) -> Result<(), &'static str> {
         match event.event_type() {
             EventType::GuardAsleep => self.asleep(event.datetime()),
             EventType::GuardAwake => self.awake(event.datetime()),
-            EventType::GuardBeginsShift(guard) => self.begins_shift(guard),
+            EventType::GuardBeginsShift(guard) => {
+                self.begins_shift(guard);
+                Ok(())
+            }
         }
     }
 
-    fn asleep(&mut self, datetime: &DateTime) {
+    fn current(&mut self) -> Result<&mut SleepTracker, &'static str> {
         self.sleep_tracker
             .get_mut(&self.current_guard)
-            .unwrap()
-            .asleep(datetime);
+            .ok_or("a guard to begin their shift first")
     }
 
-    fn awake(&mut self, datetime: &DateTime) {
-        self.sleep_tracker
-            .get_mut(&self.current_guard)
-            .unwrap()
-            .awake(datetime);
+    fn asleep(&mut self, datetime: &DateTime) -> Result<(), &'static str> {
+        self.current()?.asleep(datetime);
+        Ok(())
+    }
+
+    fn awake(&mut self, datetime: &DateTime) -> Result<(), &'static str> {
+        self.current()?.awake(datetime)
     }
 
     fn begins_shift(&mut self, guard: &GuardID) {
@@ -225,6 +234,69 @@ impl EventTracker {
         self.current_guard = *guard;
     }
 
+    // How often the given guard was asleep during each minute of the midnight hour.
+    pub fn histogram(&self, guard: GuardID) -> Vec<usize> {
+        let mut histogram = vec![0; 60];
+
+        if let Some(sleep_tracker) = self.sleep_tracker.get(&guard) {
+            for (hour, minute) in sleep_tracker.minutes_asleep() {
+                if hour == 0 && minute < 60 {
+                    histogram[minute as usize] += 1;
+                }
+            }
+        }
+
+        histogram
+    }
+
+    // Save the sleep periods of every guard for the cache of parsed inputs. The current guard and
+    // whether a guard is asleep only matter while the events are being fed to the tracker, so
+    // they are left out.
+    pub fn encode(&self, encoder: &mut Encoder) {
+        encoder.usize(self.sleep_tracker.len());
+
+        for (guard, sleep_tracker) in self.sleep_tracker.iter() {
+            encoder.u32(*guard);
+            encoder.usize(sleep_tracker.sleep_periods.len());
+
+            for period in sleep_tracker.sleep_periods.iter() {
+                let date = period.start.date();
+                let time = period.start.time();
+
+                encoder.i32(date.year());
+                encoder.u32(date.month());
+                encoder.u32(date.day());
+                encoder.u8(time.hour());
+                encoder.u8(time.minutes());
+                encoder.u32(period.duration);
+            }
+        }
+    }
+
+    // Load a tracker saved by `encode()`.
+    pub fn decode(decoder: &mut Decoder) -> common::Result<EventTracker> {
+        let mut tracker = EventTracker::new();
+
+        for _ in 0..decoder.usize()? {
+            let guard = decoder.u32()?;
+            let mut sleep_tracker = SleepTracker::new();
+
+            for _ in 0..decoder.usize()? {
+                let date = Date::new(decoder.i32()?, decoder.u32()?, decoder.u32()?);
+                let time = Time::new(decoder.u8()?, decoder.u8()?);
+                let duration = decoder.u32()?;
+
+                sleep_tracker
+                    .sleep_periods
+                    .push(SleepDuration::new(DateTime::new(date, time), duration));
+            }
+
+            tracker.sleep_tracker.insert(guard, sleep_tracker);
+        }
+
+        Ok(tracker)
+    }
+
     pub fn summaries(&self) -> Vec<GuardSummary> {
         // Our result.
         let mut result = Vec::new();
@@ -240,7 +312,7 @@ impl EventTracker {
             // Loop over each minute the given guard was asleep and sum up the count.
             for minute in sleep_tracker.minutes_asleep().iter() {
                 // Bump the value by 1.
-                *asleep_time_freq.entry(minute.clone()).or_insert(0) += 1;
+                *asleep_time_freq.entry(*minute).or_insert(0) += 1;
 
                 // Bump our sum.
                 total_minutes_asleep += 1;
@@ -252,8 +324,8 @@ impl EventTracker {
 
             for (timestamp, count) in asleep_time_freq.iter() {
                 if count > &most_missed_timestamp_count {
-                    most_missed_timestamp = timestamp.clone();
-                    most_missed_timestamp_count = count.clone();
+                    most_missed_timestamp = *timestamp;
+                    most_missed_timestamp_count = *count;
                 }
             }
 
@@ -269,6 +341,12 @@ impl EventTracker {
     }
 }
 
+impl Default for EventTracker {
+    fn default() -> EventTracker {
+        EventTracker::new()
+    }
+}
+
 struct SleepDuration {
     start: DateTime,
     duration: u32,
@@ -327,24 +405,30 @@ impl SleepTracker {
     }
 
     // We woke up.
-    pub fn awake(&mut self, other: &DateTime) {
+    pub fn awake(&mut self, other: &DateTime) -> Result<(), &'static str> {
         if let SleepTrackerState::Asleep(ref datetime) = self.state {
             // They always work on one day at a time.
-            assert_eq!(datetime.date().year(), other.date().year());
-            assert_eq!(datetime.date().month(), other.date().month());
-            assert_eq!(datetime.date().day(), other.date().day());
+            if datetime.date() != other.date() {
+                return Err("the guard to wake up on the day they fell asleep");
+            }
 
-            let delta_minutes = (other.time().hour() - datetime.time().hour()) * 60
-                + (other.time().minutes() - datetime.time().minutes());
+            let start =
+                u32::from(datetime.time().hour()) * 60 + u32::from(datetime.time().minutes());
+            let end = u32::from(other.time().hour()) * 60 + u32::from(other.time().minutes());
+
+            if end < start {
+                return Err("the guard to wake up after falling asleep");
+            }
 
             self.sleep_periods
-                .push(SleepDuration::new(datetime.clone(), delta_minutes.into()));
+                .push(SleepDuration::new(datetime.clone(), end - start));
         } else {
             // We arrived from a state which was not `Asleep`? Sounds weird.
-            assert!(false);
+            return Err("the guard to fall asleep before waking up");
         }
 
         self.state = SleepTrackerState::Awake;
+        Ok(())
     }
 
     // Returns a vector of all the minutes we have been asleep at.
@@ -358,3 +442,9 @@ impl SleepTracker {
         m
     }
 }
+
+impl Default for SleepTracker {
+    fn default() -> SleepTracker {
+        SleepTracker::new()
+    }
+}
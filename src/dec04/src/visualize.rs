@@ -0,0 +1,141 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::visualize::{heat, Figure, Svg, Visualizer};
+
+use {Dec04, EventTracker};
+
+// The width of the column with the guard IDs and the size of a minute in pixels.
+const LABEL_WIDTH: f64 = 50.0;
+const CELL_SIZE: f64 = 10.0;
+
+// Draw how often each guard was asleep during each minute of the midnight hour, one row per guard
+// with the sleepiest guard on top. The minutes picked by both strategies are outlined.
+fn sleep_chart(tracker: &EventTracker) -> String {
+    let mut summaries = tracker.summaries();
+    summaries.retain(|summary| summary.minutes_asleep() > 0);
+    summaries.sort_by(|a, b| {
+        b.minutes_asleep()
+            .cmp(&a.minutes_asleep())
+            .then(a.id().cmp(&b.id()))
+    });
+
+    let histograms: Vec<Vec<usize>> = summaries
+        .iter()
+        .map(|summary| tracker.histogram(summary.id()))
+        .collect();
+    let highest = histograms
+        .iter()
+        .flat_map(|histogram| histogram.iter().cloned())
+        .max()
+        .unwrap_or(1);
+
+    let top = 2.0 * CELL_SIZE;
+    let mut svg = Svg::new(
+        LABEL_WIDTH + 60.0 * CELL_SIZE,
+        top + summaries.len() as f64 * CELL_SIZE,
+    );
+
+    for minute in (0..60).step_by(15) {
+        let label = format!("00:{:02}", minute);
+        let x = LABEL_WIDTH + f64::from(minute) * CELL_SIZE;
+        svg.text(x, top - 6.0, "start", &label);
+    }
+
+    for (row, (summary, histogram)) in summaries.iter().zip(histograms.iter()).enumerate() {
+        let y = top + row as f64 * CELL_SIZE;
+        svg.text(
+            LABEL_WIDTH - 6.0,
+            y + CELL_SIZE - 1.0,
+            "end",
+            &format!("#{}", summary.id()),
+        );
+
+        for (minute, &count) in histogram.iter().enumerate() {
+            let fill = if count == 0 {
+                String::from("#f4f4f4")
+            } else {
+                heat(count as f64 / highest as f64)
+            };
+            let title = format!(
+                "guard #{} at 00:{:02}: asleep {} time(s)",
+                summary.id(),
+                minute,
+                count
+            );
+
+            svg.rect(
+                LABEL_WIDTH + minute as f64 * CELL_SIZE,
+                y,
+                CELL_SIZE,
+                CELL_SIZE,
+                &fill,
+                &title,
+            );
+        }
+    }
+
+    // Strategy 1 picks the sleepiest guard, strategy 2 the guard asleep most often on one minute.
+    let strategies = [
+        summaries.iter().enumerate().max_by(|a, b| {
+            a.1.minutes_asleep()
+                .cmp(&b.1.minutes_asleep())
+                .then(b.1.id().cmp(&a.1.id()))
+        }),
+        summaries.iter().enumerate().max_by(|a, b| {
+            a.1.most_missed_timestamp_count()
+                .cmp(&b.1.most_missed_timestamp_count())
+                .then(b.1.id().cmp(&a.1.id()))
+        }),
+    ];
+
+    for (row, summary) in strategies.iter().flatten() {
+        svg.frame(
+            LABEL_WIDTH + f64::from(summary.most_missed_timestamp().minutes()) * CELL_SIZE,
+            top + *row as f64 * CELL_SIZE,
+            CELL_SIZE,
+            CELL_SIZE,
+            "#2166ac",
+        );
+    }
+
+    svg.finish()
+}
+
+impl Visualizer for Dec04 {
+    fn figures(tracker: &EventTracker) -> Vec<Figure> {
+        vec![Figure {
+            title: String::from(
+                "Minutes asleep of each guard, with the minutes picked by both strategies outlined",
+            ),
+            svg: sleep_chart(tracker),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use common::Solution;
+
+    #[test]
+    fn draw_sleep_chart() {
+        let data = fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/01.in")).unwrap();
+        let tracker = Dec04::parse(&mut &data[..]).unwrap();
+        let svg = sleep_chart(&tracker);
+
+        // Guard #10 slept 50 minutes and comes first, guard #99 slept 30 minutes.
+        assert_eq!(svg.matches("time(s)</title>").count(), 120);
+        assert!(svg.find(">#10<").unwrap() < svg.find(">#99<").unwrap());
+        assert!(svg.contains("<title>guard #10 at 00:24: asleep 2 time(s)</title>"));
+        assert!(svg.contains("<title>guard #99 at 00:45: asleep 3 time(s)</title>"));
+
+        // Minute 24 of guard #10 and minute 45 of guard #99 are outlined.
+        assert!(svg.contains("<rect x=\"290\" y=\"20\" width=\"10\" height=\"10\" fill=\"none\""));
+        assert!(svg.contains("<rect x=\"500\" y=\"30\" width=\"10\" height=\"10\" fill=\"none\""));
+    }
+}
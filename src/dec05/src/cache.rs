@@ -0,0 +1,20 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::cache::{Cached, Decoder, Encoder};
+use common::Result;
+
+use Dec05;
+
+impl Cached for Dec05 {
+    const VERSION: u32 = 1;
+
+    fn encode(content: &String, encoder: &mut Encoder) {
+        encoder.str(content);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<String> {
+        Ok(decoder.str()?.to_string())
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::explore::{self, Explorer};
+use common::Result;
+
+use {reduce_with_filter, unique_characters, Dec05};
+
+impl Explorer for Dec05 {
+    fn commands() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("length", "number of units in the polymer"),
+            ("units", "the unit types of the polymer"),
+            (
+                "reduce [UNIT]",
+                "length of the fully reacted polymer, optionally with UNIT removed first",
+            ),
+        ]
+    }
+
+    fn execute(content: &String, command: &str, args: &[&str]) -> Result<String> {
+        match command {
+            "length" => Ok(content.len().to_string()),
+            "units" => {
+                let mut units: Vec<char> = unique_characters(content).into_iter().collect();
+                units.sort();
+
+                Ok(units.into_iter().collect())
+            }
+            "reduce" => {
+                let filter: Option<char> = match args.first() {
+                    Some(_) => Some(explore::argument(args, 0, "UNIT")?),
+                    None => None,
+                };
+
+                Ok(reduce_with_filter(content, filter).len().to_string())
+            }
+            _ => Err(explore::unknown(command)),
+        }
+    }
+}
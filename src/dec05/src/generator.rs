@@ -0,0 +1,99 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::io::Write;
+
+use common::generate::{Generator, Knobs, Rng};
+use common::{Error, Result};
+
+use Dec05;
+
+// Size of the chunks the polymer is written in.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+impl Generator for Dec05 {
+    fn knobs() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("size", "number of units in the polymer (default 50000)"),
+            ("types", "number of distinct unit types (default 26)"),
+            (
+                "reaction",
+                "probability that a unit reacts with the unit before it (default 0.3)",
+            ),
+        ]
+    }
+
+    fn generate(rng: &mut Rng, knobs: &Knobs, writer: &mut dyn Write) -> Result<()> {
+        let size = knobs.get("size", 50_000u64)?;
+        let types = knobs.get("types", 26u64)?;
+        let reaction = knobs.probability("reaction", 0.3)?;
+
+        if types == 0 || types > 26 {
+            return Err(Error::invalid("types must be between 1 and 26"));
+        }
+
+        // The polymer is written in chunks such that gigabyte sized polymers can be generated
+        // without keeping them in memory.
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        let mut previous: Option<u8> = None;
+
+        for _ in 0..size {
+            let unit = match previous {
+                Some(unit) if rng.chance(reaction) => unit ^ 0x20,
+                _ => {
+                    let unit = b'a' + rng.below(types) as u8;
+
+                    if rng.chance(0.5) {
+                        unit.to_ascii_uppercase()
+                    } else {
+                        unit
+                    }
+                }
+            };
+
+            chunk.push(unit);
+            previous = Some(unit);
+
+            if chunk.len() == CHUNK_SIZE {
+                writer.write_all(&chunk)?;
+                chunk.clear();
+            }
+        }
+
+        chunk.push(b'\n');
+        writer.write_all(&chunk)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::generate;
+    use common::Solution;
+
+    #[test]
+    fn generated_input_parses() {
+        let knobs = Knobs::parse(&["size=100000", "types=4"]).unwrap();
+        let data = generate::generate::<Dec05>(7, &knobs).unwrap();
+
+        assert_eq!(data, generate::generate::<Dec05>(7, &knobs).unwrap());
+
+        let polymer = Dec05::parse(&mut &data[..]).unwrap();
+        assert_eq!(polymer.len(), 100_000);
+        assert!(polymer.chars().all(|c| "abcdABCD".contains(c)));
+        assert!(Dec05::part1(&polymer).unwrap() < 100_000);
+    }
+
+    #[test]
+    fn reactions() {
+        let knobs = Knobs::parse(&["size=1000", "reaction=1"]).unwrap();
+        let data = generate::generate::<Dec05>(7, &knobs).unwrap();
+        let polymer = Dec05::parse(&mut &data[..]).unwrap();
+
+        assert_eq!(Dec05::part1(&polymer).unwrap(), 0);
+    }
+}
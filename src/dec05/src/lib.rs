@@ -0,0 +1,220 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+#[macro_use]
+extern crate common;
+
+mod cache;
+mod explore;
+mod generator;
+mod metadata;
+
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use common::normalize;
+use common::visualize::Visualizer;
+use common::{Error, Result, Solution};
+
+// Returns the reversed case of the given character. If the input character is lowercased we return
+// the uppercased version and if the input character is uppercased we return the lowercased
+// version. Characters without an ASCII case are returned as they are.
+pub fn reverse_case(c: char) -> char {
+    if c.is_ascii_lowercase() {
+        c.to_ascii_uppercase()
+    } else {
+        c.to_ascii_lowercase()
+    }
+}
+
+// O(n) algorithm that returns a set of lower cased characters that is used in the given input
+// string. Only ASCII letters are lower cased.
+pub fn unique_characters(input: &str) -> HashSet<char> {
+    let mut result = HashSet::new();
+
+    for character in input.chars() {
+        result.insert(character.to_ascii_lowercase());
+    }
+
+    result
+}
+
+// React the given polymer until no units react anymore and return what is left of it. If a filter
+// is given, the units of that type are removed regardless of their polarity. Only ASCII letters
+// react, any other character is left in place.
+pub fn reduce_with_filter(input: &str, filter: Option<char>) -> String {
+    // Our resulting string. We allocate with a capacity equal to the size of the input string even
+    // though our result is going to be smaller, but because of this we can avoid having to grow
+    // the internal buffer when pushing characters onto the result.
+    let mut result = String::with_capacity(input.len());
+
+    for character in input.chars() {
+        // If we have a filter, check if our character matches the character to filter. If it does
+        // we continue processing our string without adding our character to the result.
+        if let Some(filter_character) = filter {
+            if character.eq_ignore_ascii_case(&filter_character) {
+                continue;
+            }
+        }
+
+        // If we have had a character before (this is only false for the first character in the
+        // input string) we check if our current character is equal to the previous character but
+        // where their ASCII casing is reversed. If that is the case, we remove the last character
+        // from our result string and continue processing our list of characters with the current
+        // character discarded. Characters without a case are their own reverse and never react.
+        if let Some(last_character) = result.chars().last() {
+            if last_character != character && last_character == reverse_case(character) {
+                result.pop();
+                continue;
+            }
+        }
+
+        result.push(character);
+    }
+
+    result
+}
+
+// React the given polymer without removing any units first.
+pub fn reduce(input: &str) -> String {
+    reduce_with_filter(input, None)
+}
+
+pub fn compute_result_task1(input: &str) -> usize {
+    let length = reduce(input).len();
+    trace!("polymer reduced", "units" => input.len(), "remaining" => length);
+
+    length
+}
+
+pub fn compute_result_task2(input: &str) -> usize {
+    let mut characters: Vec<char> = unique_characters(input).into_iter().collect();
+    characters.sort();
+
+    // Removing a unit type that is in the polymer always makes it shorter, so the length of the
+    // polymer is the answer only if it has no units at all.
+    let mut result = input.len();
+    let mut removed = None;
+
+    for character in characters {
+        let length = reduce_with_filter(input, Some(character)).len();
+        trace!("unit type removed", "unit" => character, "remaining" => length);
+
+        if length < result {
+            result = length;
+            removed = Some(character);
+        }
+    }
+
+    if let Some(character) = removed {
+        trace!("best unit type to remove", "unit" => character, "remaining" => result);
+    }
+
+    result
+}
+
+pub struct Dec05;
+
+impl Solution for Dec05 {
+    type Input = String;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<String> {
+        let content = normalize::text(reader)?;
+
+        // The reduction only knows how to react ASCII letters with each other.
+        if let Some(index) = content.find(|c: char| !c.is_ascii_alphabetic()) {
+            let start = content[..index].rfind('\n').map_or(0, |i| i + 1);
+            let line = content[..index].matches('\n').count() + 1;
+            let text = content[start..].lines().next().unwrap_or("");
+            let column = content[start..index].chars().count() + 1;
+
+            return Err(Error::syntax(line, column, text, "a polymer unit"));
+        }
+
+        Ok(content)
+    }
+
+    fn part1(content: &String) -> Result<usize> {
+        Ok(compute_result_task1(content))
+    }
+
+    fn part2(content: &String) -> Result<usize> {
+        Ok(compute_result_task2(content))
+    }
+}
+
+// The polymer has nothing worth drawing.
+impl Visualizer for Dec05 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_case_test() {
+        assert_eq!(reverse_case('A'), 'a');
+        assert_eq!(reverse_case('a'), 'A');
+        assert_eq!(reverse_case('B'), 'b');
+        assert_eq!(reverse_case('b'), 'B');
+        assert_eq!(reverse_case('@'), '@');
+    }
+
+    #[test]
+    fn unique_characters_test() {
+        let h = unique_characters("AAAaaaBBBcCcbbb@@@");
+
+        assert_eq!(h.len(), 4);
+        assert!(h.contains(&'a'));
+        assert!(h.contains(&'b'));
+        assert!(h.contains(&'c'));
+        assert!(h.contains(&'@'));
+    }
+
+    #[test]
+    fn known_results_task1() {
+        assert_eq!(reduce("dabAcCaCBAcCcaDA"), "dabCBAcaDA");
+        assert_eq!(compute_result_task1("dabAcCaCBAcCcaDA"), 10);
+    }
+
+    #[test]
+    fn known_results_task2() {
+        assert_eq!(reduce_with_filter("dabAcCaCBAcCcaDA", Some('a')), "dbCBcD");
+        assert_eq!(
+            reduce_with_filter("dabAcCaCBAcCcaDA", Some('b')),
+            "daCAcaDA"
+        );
+        assert_eq!(reduce_with_filter("dabAcCaCBAcCcaDA", Some('c')), "daDA");
+        assert_eq!(reduce_with_filter("dabAcCaCBAcCcaDA", Some('d')), "abCBAc");
+
+        assert_eq!(compute_result_task2("dabAcCaCBAcCcaDA"), 4);
+    }
+
+    #[test]
+    fn other_characters_do_not_react() {
+        assert_eq!(reverse_case('é'), 'é');
+        assert_eq!(reduce("aAéÉé@@bB"), "éÉé@@");
+        assert_eq!(reduce_with_filter("xéXé", Some('x')), "éé");
+        assert_eq!(compute_result_task2("éaAé"), 0);
+    }
+
+    #[test]
+    fn empty_polymer() {
+        let content = Dec05::parse(&mut "".as_bytes()).unwrap();
+
+        assert_eq!(Dec05::part1(&content).unwrap(), 0);
+        assert_eq!(Dec05::part2(&content).unwrap(), 0);
+    }
+
+    #[test]
+    fn parse_normalizes_input() {
+        assert_eq!(Dec05::parse(&mut "dabAc".as_bytes()).unwrap(), "dabAc");
+        assert_eq!(
+            Dec05::parse(&mut "dabAc\r\n\n".as_bytes()).unwrap(),
+            "dabAc"
+        );
+        assert!(Dec05::parse(&mut "dab\nAc\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use common::metadata::{Answer, Metadata, Puzzle};
+
+use Dec05;
+
+impl Metadata for Dec05 {
+    fn puzzle() -> Puzzle {
+        Puzzle {
+            year: 2018,
+            day: 5,
+            title: "Alchemical Reduction",
+            parts: [
+                (
+                    "the units left after fully reacting the polymer",
+                    Answer::Integer,
+                ),
+                (
+                    "the length of the shortest polymer after removing all units of one type",
+                    Answer::Integer,
+                ),
+            ],
+            input: "a single line with the polymer, a string of upper and lowercase letters",
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::collections::HashMap;
+use std::fs;
+
+use common::{Error, Part, Result};
+
+use days::FIRST_YEAR;
+
+// The checked-in answers file of the repository.
+pub const ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../answers.txt");
+
+// The known answers to our puzzle inputs.
+//
+// The answers file contains one answer per line in the form `<year> <day> <part> <answer>`, for
+// example `2018 dec01 1 587`. Lines without a year, in the form the file had before there was more
+// than one year, are answers of the first year. Empty lines and lines starting with '#' are
+// ignored.
+pub struct Answers {
+    answers: HashMap<(u32, String, Part), String>,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Answers> {
+        Answers::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(content: &str) -> Result<Answers> {
+        let mut answers = HashMap::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // A day is never named after a number, so a leading number is the year.
+            let (year, rest) = match line.split_once(char::is_whitespace) {
+                Some((year, rest)) if year.bytes().all(|b| b.is_ascii_digit()) => {
+                    match year.parse::<u32>() {
+                        Ok(year) => (year, rest.trim_start()),
+                        Err(_) => return Err(Error::parse(index + 1, line, "invalid year")),
+                    }
+                }
+                _ => (FIRST_YEAR, line),
+            };
+
+            let fields: Vec<&str> = rest.splitn(3, char::is_whitespace).collect();
+
+            let (day, part, answer) = match fields.as_slice() {
+                [day, part, answer] => match part.parse::<Part>() {
+                    Ok(part) => (day.to_string(), part, answer.trim().to_string()),
+                    Err(_) => return Err(Error::parse(index + 1, line, "invalid part")),
+                },
+                _ => return Err(Error::parse(index + 1, line, "invalid answer")),
+            };
+
+            if answers.insert((year, day, part), answer).is_some() {
+                return Err(Error::parse(index + 1, line, "duplicate answer"));
+            }
+        }
+
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, year: u32, day: &str, part: Part) -> Option<&str> {
+        self.answers
+            .get(&(year, day.to_string(), part))
+            .map(|s| s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use common::metadata::Answer;
+
+    #[test]
+    fn parse_answers() {
+        let answers =
+            Answers::parse("# Comment\n\n2018 dec01 1 587\n2019 dec01 1 3317\ndec02 2 fgij\n")
+                .unwrap();
+
+        assert_eq!(answers.get(2018, "dec01", Part::One), Some("587"));
+        assert_eq!(answers.get(2018, "dec01", Part::Two), None);
+        assert_eq!(answers.get(2019, "dec01", Part::One), Some("3317"));
+        assert_eq!(answers.get(2018, "dec02", Part::Two), Some("fgij"));
+        assert_eq!(answers.get(2019, "dec02", Part::Two), None);
+    }
+
+    #[test]
+    fn parse_invalid_answers() {
+        assert!(Answers::parse("dec01 1\n").is_err());
+        assert!(Answers::parse("dec01 3 587\n").is_err());
+        assert!(Answers::parse("dec01 1 587\ndec01 1 588\n").is_err());
+        assert!(Answers::parse("dec01 1 587\n2018 dec01 1 588\n").is_err());
+        assert!(Answers::parse("2018 dec01 1\n").is_err());
+        assert!(Answers::parse("99999999999 dec01 1 587\n").is_err());
+    }
+
+    #[test]
+    fn checked_in_answers_parse() {
+        let answers = Answers::load(ANSWERS).unwrap();
+
+        // The answers also have to be of the kind the puzzles ask for. A day that has not been
+        // solved yet has no answers, which `verify` reports as unknown.
+        for day in ::days::all() {
+            for part in Part::all() {
+                let answer = match answers.get(day.year(), day.name(), part) {
+                    Some(answer) => answer,
+                    None => continue,
+                };
+
+                if day.puzzle().answer(part) == Answer::Integer {
+                    assert!(answer.parse::<i64>().is_ok(), "{}: {}", day.label(), answer);
+                }
+            }
+        }
+    }
+}
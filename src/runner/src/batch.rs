@@ -0,0 +1,187 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Runs many inputs of many days on a pool of threads.
+//
+// The inputs are kept in a directory with a subdirectory per year and in there a subdirectory per
+// day, named after the day, with one file per input in each. For example
+// `inputs/2018/dec01/alice.txt`. The days of the first year may also be kept directly in the
+// directory, as in `inputs/dec01/alice.txt`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::cache::Cache;
+use common::hash;
+use common::input::Source;
+use common::normalize;
+use common::{Error, Part, Result};
+
+use days::{Day, FIRST_YEAR};
+
+// A single input of a day.
+pub struct Job<'a> {
+    pub day: &'a Day,
+    pub path: PathBuf,
+}
+
+// The answers of both parts of a single input and the time each phase took.
+pub struct Answers {
+    pub input_hash: String,
+    pub parse: Duration,
+    pub parts: Vec<(String, Duration)>,
+
+    // Warnings about the input, such as CRLF line endings that had to be removed.
+    pub warnings: Vec<String>,
+}
+
+// Find the inputs of the given days in the given directory. Days without a subdirectory are
+// skipped. The inputs of each day are sorted by name.
+pub fn discover<'a>(directory: &Path, days: &[&'a Day]) -> Result<Vec<Job<'a>>> {
+    let mut jobs = Vec::new();
+
+    // Fail early if the directory itself is missing rather than reporting that it is empty.
+    fs::read_dir(directory)?;
+
+    for day in days {
+        let mut subdirectory = directory.join(day.year().to_string()).join(day.name());
+
+        if !subdirectory.is_dir() && day.year() == FIRST_YEAR {
+            subdirectory = directory.join(day.name());
+        }
+
+        if !subdirectory.is_dir() {
+            continue;
+        }
+
+        let mut paths = Vec::new();
+
+        for entry in fs::read_dir(&subdirectory)? {
+            let path = entry?.path();
+
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+
+        paths.sort();
+        jobs.extend(paths.into_iter().map(|path| Job { day, path }));
+    }
+
+    Ok(jobs)
+}
+
+fn solve(job: &Job, cache: Option<&Cache>) -> Result<Answers> {
+    let data = Source::Path(job.path.clone()).read()?;
+
+    let start = Instant::now();
+    let (input, warnings) = normalize::capture(|| job.day.load(&data, cache));
+    let input = input?;
+    let parse = start.elapsed();
+
+    let mut parts = Vec::new();
+
+    for part in Part::all() {
+        let start = Instant::now();
+        let answer = job.day.solve(&*input, part)?;
+        parts.push((answer, start.elapsed()));
+    }
+
+    Ok(Answers {
+        input_hash: hash::hex(&data),
+        parse,
+        parts,
+        warnings,
+    })
+}
+
+// Solve every job using the given number of threads. The results are returned in the order of
+// the jobs. A failing job does not affect the other jobs.
+pub fn run(jobs: &[Job], threads: usize, cache: Option<&Cache>) -> Vec<Result<Answers>> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<Answers>>>> =
+        Mutex::new((0..jobs.len()).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.min(jobs.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+
+                if index >= jobs.len() {
+                    break;
+                }
+
+                let result = solve(&jobs[index], cache);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|| Err(Error::invalid("the job was never run"))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::process;
+
+    use days;
+
+    #[test]
+    fn run_batch() {
+        let directory = env::temp_dir().join(format!("aoc-batch-{}", process::id()));
+        fs::create_dir_all(directory.join("2018/dec01")).unwrap();
+        fs::write(directory.join("2018/dec01/a.txt"), "+1\n-2\n+3\n+1\n").unwrap();
+        fs::write(directory.join("2018/dec01/b.txt"), "+1\nfoo\n").unwrap();
+
+        // The flat layout of the first year is only used for days without a year directory.
+        fs::create_dir_all(directory.join("dec01")).unwrap();
+        fs::write(directory.join("dec01/c.txt"), "+1\n").unwrap();
+        fs::create_dir_all(directory.join("dec02")).unwrap();
+        fs::write(directory.join("dec02/d.txt"), "abcdef\n").unwrap();
+
+        let all = days::all();
+        let selected: Vec<&Day> = all.iter().collect();
+        let jobs = discover(&directory, &selected).unwrap();
+        let results = run(&jobs, 4, None);
+
+        fs::remove_dir_all(&directory).unwrap();
+
+        let paths: Vec<&Path> = jobs
+            .iter()
+            .map(|job| job.path.strip_prefix(&directory).unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("2018/dec01/a.txt"),
+                Path::new("2018/dec01/b.txt"),
+                Path::new("dec02/d.txt"),
+            ]
+        );
+        assert_eq!(results.len(), 3);
+
+        let answers: Vec<&str> = results[0]
+            .as_ref()
+            .unwrap()
+            .parts
+            .iter()
+            .map(|(answer, _)| answer.as_str())
+            .collect();
+        assert_eq!(answers, vec!["3", "2"]);
+
+        assert_eq!(results[1].as_ref().err().unwrap().exit_code(), 4);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use common::cache::Cache;
+use common::{Part, Result};
+
+use days::Day;
+use memory::{self, Usage};
+
+// Summary of a set of timing samples.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Statistics {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    stddev: Duration,
+}
+
+impl Statistics {
+    pub fn new(samples: &[Duration]) -> Statistics {
+        assert!(!samples.is_empty());
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Statistics {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    pub fn median(&self) -> Duration {
+        self.median
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.mean
+    }
+
+    pub fn stddev(&self) -> Duration {
+        self.stddev
+    }
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10}  median {:>10}  mean {:>10}  stddev {:>10}",
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.mean),
+            format_duration(self.stddev)
+        )
+    }
+}
+
+// Format a duration using the largest unit that keeps the value above 1.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.3}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.3}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.3}s", nanos as f64 / 1e9)
+    }
+}
+
+// Run the given closure `iterations` times, after one untimed warm-up run, and return the
+// duration of each timed run.
+fn sample<T, F>(iterations: usize, mut f: F) -> Result<Vec<Duration>>
+where
+    F: FnMut() -> Result<T>,
+{
+    black_box(f()?);
+
+    let mut samples = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(f()?);
+        samples.push(start.elapsed());
+    }
+
+    Ok(samples)
+}
+
+// Measure the memory usage of a single run of the given closure if we have been asked to.
+fn measure<T, F>(memory: bool, f: F) -> Result<Option<Usage>>
+where
+    F: FnOnce() -> Result<T>,
+{
+    if !memory {
+        return Ok(None);
+    }
+
+    let (result, usage) = memory::measure(f);
+    result?;
+
+    Ok(Some(usage))
+}
+
+// The timings of one phase of a day.
+pub struct Timing {
+    // The phase: "parse", "part 1" or "part 2".
+    pub phase: String,
+
+    // The implementation used for the phase.
+    pub implementation: &'static str,
+
+    pub statistics: Statistics,
+
+    // The memory used by a single run of the phase, if it was measured.
+    pub memory: Option<Usage>,
+}
+
+// Time the parse, part 1 and part 2 phases of the given day separately. Every implementation of
+// each part is timed. Every phase is run `iterations` times over the given input data. With a
+// cache the parse phase measures loading the parsed input from the cache instead, as the warm up
+// run fills the cache. With `memory` every phase is run once more to measure its memory usage,
+// such that the accounting does not affect the timings.
+pub fn run(
+    day: &Day,
+    data: &[u8],
+    iterations: usize,
+    cache: Option<&Cache>,
+    memory: bool,
+) -> Result<Vec<Timing>> {
+    let mut result = Vec::new();
+
+    let samples = sample(iterations, || day.load(data, cache))?;
+    result.push(Timing {
+        phase: String::from("parse"),
+        implementation: if cache.is_some() { "cached" } else { "default" },
+        statistics: Statistics::new(&samples),
+        memory: measure(memory, || day.load(data, cache))?,
+    });
+
+    let input = day.load(data, cache)?;
+
+    for part in Part::all() {
+        for (index, implementation) in day.implementations(part).into_iter().enumerate() {
+            let samples = sample(iterations, || day.solve_with(&*input, part, index))?;
+            result.push(Timing {
+                phase: format!("part {}", part),
+                implementation,
+                statistics: Statistics::new(&samples),
+                memory: measure(memory, || day.solve_with(&*input, part, index))?,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics() {
+        let samples: Vec<Duration> = [4, 1, 3, 2]
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect();
+        let s = Statistics::new(&samples);
+
+        assert_eq!(s.min, Duration::from_millis(1));
+        assert_eq!(s.median, Duration::from_micros(2500));
+        assert_eq!(s.mean, Duration::from_micros(2500));
+
+        // The population standard deviation of 1, 2, 3, 4 is sqrt(1.25).
+        let stddev = s.stddev.as_secs_f64() * 1e3;
+        assert!((stddev - 1.25f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn format_durations() {
+        assert_eq!(format_duration(Duration::from_nanos(12)), "12ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_500)), "1.500µs");
+        assert_eq!(format_duration(Duration::from_micros(2_250)), "2.250ms");
+        assert_eq!(format_duration(Duration::from_millis(3_000)), "3.000s");
+    }
+}
@@ -0,0 +1,394 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::any::Any;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use common::cache::{self, Cache, Cached};
+use common::explore::Explorer;
+use common::generate::{Generator, Knobs, Rng};
+use common::input::Source;
+use common::metadata::{Metadata, Puzzle};
+use common::visualize::{Figure, Visualizer};
+use common::{Part, Solution};
+
+use dec01::Dec01;
+use dec02::Dec02;
+use dec03::Dec03;
+use dec04::Dec04;
+use dec05::Dec05;
+
+// The first year we solved. Its crates live directly in `src` and are named after the day alone,
+// while those of later years live in `src/<year>` and have the year in their package name.
+pub const FIRST_YEAR: u32 = common::input::FIRST_YEAR;
+
+// Parses an input of the named day through the cache of parsed inputs.
+type ParseCached = fn(&Cache, &str, &[u8]) -> common::Result<Box<dyn Any>>;
+
+// A registered day. The solution is type erased such that the runner can handle every day the
+// same way regardless of what the parsed input and the answers look like.
+pub struct Day {
+    // The year of the event and the day of the month.
+    year: u32,
+    number: u32,
+
+    // The name of the day within its year, such as "dec03".
+    name: &'static str,
+
+    // The default input file of the day.
+    input: &'static str,
+
+    parse: fn(&mut dyn BufRead) -> common::Result<Box<dyn Any>>,
+    parse_cached: ParseCached,
+    implementations: fn(Part) -> Vec<&'static str>,
+    solve: fn(&dyn Any, Part, usize) -> common::Result<String>,
+    streaming_parts: fn() -> Vec<Part>,
+    stream: fn(&mut dyn BufRead, Part) -> common::Result<String>,
+    knobs: fn() -> Vec<(&'static str, &'static str)>,
+    generate: fn(&mut Rng, &Knobs, &mut dyn Write) -> common::Result<()>,
+    commands: fn() -> Vec<(&'static str, &'static str)>,
+    execute: fn(&dyn Any, &str, &[&str]) -> common::Result<String>,
+    puzzle: fn() -> Puzzle,
+    figures: fn(&dyn Any) -> Vec<Figure>,
+
+    examples: fn(&Path) -> common::Result<Vec<String>>,
+
+    // Only the tests run the fuzz corpus of a day.
+    #[cfg(test)]
+    pipeline: fn(&[u8]),
+}
+
+impl Day {
+    fn new<S: Solution + Cached + Generator + Explorer + Metadata + Visualizer>(
+        year: u32,
+        number: u32,
+        name: &'static str,
+        input: &'static str,
+    ) -> Day
+    where
+        S::Input: 'static,
+    {
+        Day {
+            year,
+            number,
+            name,
+            input,
+            parse: parse::<S>,
+            parse_cached: parse_cached::<S>,
+            implementations: common::implementations::<S>,
+            solve: solve::<S>,
+            streaming_parts: S::streaming_parts,
+            stream: common::stream::<S>,
+            knobs: S::knobs,
+            generate: S::generate,
+            commands: S::commands,
+            execute: execute::<S>,
+            puzzle: S::puzzle,
+            figures: figures::<S>,
+            examples: common::examples::check::<S>,
+            #[cfg(test)]
+            pipeline: common::fuzz::pipeline::<S>,
+        }
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    // The day as it is shown to the user, such as 2018/dec03.
+    pub fn label(&self) -> String {
+        format!("{}/{}", self.year, self.name)
+    }
+
+    pub fn input(&self) -> &'static str {
+        self.input
+    }
+
+    // The name of the package implementing the day.
+    pub fn package(&self) -> String {
+        package(self.year, self.name)
+    }
+
+    // The directory of the crate implementing the day, relative to `src`.
+    pub fn directory(&self) -> String {
+        directory(self.year, self.name)
+    }
+
+    // Figure out where the input of the day should be read from, see `Source::resolve()`.
+    pub fn source(&self, argument: Option<&str>) -> Source {
+        Source::resolve(argument, self.year, self.name, self.input())
+    }
+
+    pub fn parse(&self, reader: &mut dyn BufRead) -> common::Result<Box<dyn Any>> {
+        (self.parse)(reader)
+    }
+
+    // Parse the given input, going through the cache of parsed inputs if one is given.
+    pub fn load(&self, data: &[u8], cache: Option<&Cache>) -> common::Result<Box<dyn Any>> {
+        match cache {
+            Some(cache) => (self.parse_cached)(cache, &self.package(), data),
+            None => self.parse(&mut &data[..]),
+        }
+    }
+
+    // The names of the implementations of the given part. The first name is the default.
+    pub fn implementations(&self, part: Part) -> Vec<&'static str> {
+        (self.implementations)(part)
+    }
+
+    // Compute the answer of the given part using the default implementation.
+    pub fn solve(&self, input: &dyn Any, part: Part) -> common::Result<String> {
+        (self.solve)(input, part, 0)
+    }
+
+    // Compute the answer of the given part using the implementation at the given index of
+    // `implementations()`.
+    pub fn solve_with(&self, input: &dyn Any, part: Part, index: usize) -> common::Result<String> {
+        (self.solve)(input, part, index)
+    }
+
+    // The parts that can be computed in a single pass over the input using `stream()`.
+    pub fn streaming_parts(&self) -> Vec<Part> {
+        (self.streaming_parts)()
+    }
+
+    // Compute the answer of the given part while reading the input from the given reader.
+    pub fn stream(&self, reader: &mut dyn BufRead, part: Part) -> common::Result<String> {
+        (self.stream)(reader, part)
+    }
+
+    // The knobs of the input generator as pairs of name and description.
+    pub fn knobs(&self) -> Vec<(&'static str, &'static str)> {
+        (self.knobs)()
+    }
+
+    // Write a synthetic puzzle input to the given writer.
+    pub fn generate(
+        &self,
+        rng: &mut Rng,
+        knobs: &Knobs,
+        writer: &mut dyn Write,
+    ) -> common::Result<()> {
+        (self.generate)(rng, knobs, writer)
+    }
+
+    // The commands for exploring a parsed input as pairs of usage and description.
+    pub fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        (self.commands)()
+    }
+
+    // Run a single exploration command against the given parsed input.
+    pub fn execute(&self, input: &dyn Any, command: &str, args: &[&str]) -> common::Result<String> {
+        (self.execute)(input, command, args)
+    }
+
+    // The description of the puzzle of the day.
+    pub fn puzzle(&self) -> Puzzle {
+        (self.puzzle)()
+    }
+
+    // Draw the figures of the given parsed input.
+    pub fn figures(&self, input: &dyn Any) -> Vec<Figure> {
+        (self.figures)(input)
+    }
+
+    // Run the examples in the given directory with every implementation and return a description
+    // of every example that did not give the expected answer.
+    pub fn examples(&self, directory: &Path) -> common::Result<Vec<String>> {
+        (self.examples)(directory)
+    }
+}
+
+fn parse<S: Solution>(reader: &mut dyn BufRead) -> common::Result<Box<dyn Any>>
+where
+    S::Input: 'static,
+{
+    Ok(Box::new(S::parse(reader)?))
+}
+
+fn parse_cached<S: Cached>(cache: &Cache, name: &str, data: &[u8]) -> common::Result<Box<dyn Any>>
+where
+    S::Input: 'static,
+{
+    Ok(Box::new(cache::parse::<S>(cache, name, data)?))
+}
+
+fn solve<S: Solution>(input: &dyn Any, part: Part, index: usize) -> common::Result<String>
+where
+    S::Input: 'static,
+{
+    common::solve_with::<S>(input.downcast_ref::<S::Input>().unwrap(), part, index)
+}
+
+fn execute<S: Explorer>(input: &dyn Any, command: &str, args: &[&str]) -> common::Result<String>
+where
+    S::Input: 'static,
+{
+    S::execute(input.downcast_ref::<S::Input>().unwrap(), command, args)
+}
+
+fn figures<S: Visualizer>(input: &dyn Any) -> Vec<Figure>
+where
+    S::Input: 'static,
+{
+    S::figures(input.downcast_ref::<S::Input>().unwrap())
+}
+
+// The name of the package of the given day of the given year.
+pub fn package(year: u32, name: &str) -> String {
+    if year == FIRST_YEAR {
+        name.to_string()
+    } else {
+        format!("aoc{}-{}", year, name)
+    }
+}
+
+// The directory of the crate of the given day of the given year, relative to `src`.
+pub fn directory(year: u32, name: &str) -> String {
+    if year == FIRST_YEAR {
+        name.to_string()
+    } else {
+        format!("{}/{}", year, name)
+    }
+}
+
+// Every day we know about, in calendar order.
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::new::<Dec01>(2018, 1, "dec01", input!("dec01")),
+        Day::new::<Dec02>(2018, 2, "dec02", input!("dec02")),
+        Day::new::<Dec03>(2018, 3, "dec03", input!("dec03")),
+        Day::new::<Dec04>(2018, 4, "dec04", input!("dec04")),
+        Day::new::<Dec05>(2018, 5, "dec05", input!("dec05")),
+    ]
+}
+
+// The years of the given days, in order.
+pub fn years(days: &[Day]) -> Vec<u32> {
+    let mut years: Vec<u32> = days.iter().map(|day| day.year).collect();
+    years.sort();
+    years.dedup();
+    years
+}
+
+// Look up a day of the given year either by its number ("3") or by its name ("dec03"). The year
+// can be overridden by prefixing the day with another one, as in "2018/3".
+pub fn find(days: &[Day], year: u32, s: &str) -> Option<usize> {
+    let (year, s) = match s.split_once('/') {
+        Some((year, s)) => (year.parse::<u32>().ok()?, s),
+        None => (year, s),
+    };
+
+    days.iter().position(|day| {
+        day.year == year && (day.name == s || s.parse::<u32>().ok() == Some(day.number))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::panic;
+    use std::path::PathBuf;
+
+    // The root of the workspace.
+    fn root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+    }
+
+    #[test]
+    fn find_known_days() {
+        let days = all();
+
+        assert_eq!(find(&days, 2018, "1"), Some(0));
+        assert_eq!(find(&days, 2018, "03"), Some(2));
+        assert_eq!(find(&days, 2018, "dec05"), Some(4));
+        assert_eq!(find(&days, 2018, "dec42"), None);
+        assert_eq!(find(&days, 2018, "foo"), None);
+        assert_eq!(find(&days, 2017, "1"), None);
+        assert_eq!(find(&days, 2017, "2018/dec02"), Some(1));
+        assert_eq!(find(&days, 2018, "2017/2"), None);
+        assert_eq!(find(&days, 2018, "x/2"), None);
+    }
+
+    #[test]
+    fn puzzles_match_days() {
+        for day in all() {
+            let puzzle = day.puzzle();
+
+            assert_eq!((puzzle.year, puzzle.day), (day.year(), day.number()));
+            assert!(!puzzle.title.is_empty());
+        }
+    }
+
+    #[test]
+    fn years_and_layout() {
+        assert_eq!(years(&all())[0], FIRST_YEAR);
+
+        assert_eq!(package(2018, "dec03"), "dec03");
+        assert_eq!(package(2019, "dec03"), "aoc2019-dec03");
+        assert_eq!(directory(2018, "dec03"), "dec03");
+        assert_eq!(directory(2019, "dec03"), "2019/dec03");
+    }
+
+    // Every day passes the examples from its puzzle description with every implementation.
+    #[test]
+    fn examples() {
+        let mut failures = Vec::new();
+
+        for day in all() {
+            let directory = root().join("src").join(day.directory()).join("examples");
+
+            match day.examples(&directory) {
+                Ok(mismatches) => {
+                    for mismatch in mismatches {
+                        failures.push(format!("{} {}", day.label(), mismatch));
+                    }
+                }
+                Err(e) => failures.push(format!("{}: {}", day.label(), e)),
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "{} example(s) failed:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+
+    // No input of the fuzz corpus of a day makes it panic. Days without a corpus are skipped.
+    #[test]
+    fn fuzz_corpus() {
+        for day in all() {
+            let directory = root().join("fuzz/corpus").join(day.package());
+
+            let entries = match fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+
+            let mut paths: Vec<PathBuf> = entries.map(|entry| entry.unwrap().path()).collect();
+            paths.sort();
+
+            for path in paths {
+                let data = fs::read(&path).unwrap();
+
+                if panic::catch_unwind(|| (day.pipeline)(&data)).is_err() {
+                    panic!("{}: the pipeline panicked", path.display());
+                }
+            }
+        }
+    }
+}
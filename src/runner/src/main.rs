@@ -0,0 +1,1107 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+extern crate common;
+extern crate dec01;
+extern crate dec02;
+extern crate dec03;
+extern crate dec04;
+extern crate dec05;
+extern crate rustyline;
+
+use std::env;
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::cache::Cache;
+use common::generate::{Knobs, Rng};
+use common::hash::{self, HashingReader};
+use common::input::Source;
+use common::normalize;
+use common::trace;
+use common::{Part, Result};
+
+// Returns the path of the default input file of the given day crate.
+macro_rules! input {
+    ($name:expr) => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../", $name, "/input/data.txt")
+    };
+}
+
+mod answers;
+mod batch;
+mod bench;
+mod days;
+mod memory;
+mod output;
+mod repl;
+mod report;
+mod scaffold;
+mod tracing;
+mod watch;
+
+use answers::Answers;
+use days::Day;
+use output::{Format, Output, Record};
+use tracing::Tracer;
+
+// Number of iterations used by `aoc bench` unless `--iterations` is given.
+const DEFAULT_ITERATIONS: usize = 10;
+
+// File written by `aoc report` unless `--output` is given.
+const DEFAULT_REPORT: &str = "report.html";
+
+// Seed used by `aoc generate` unless `--seed` is given.
+const DEFAULT_SEED: u64 = 2018;
+
+// Milliseconds between two polls of `aoc watch` unless `--interval` is given.
+const DEFAULT_INTERVAL: u64 = 500;
+
+fn usage() -> ! {
+    eprintln!("Usage: aoc list [--year YYYY]");
+    eprintln!("       aoc info [DAY] [--format FORMAT]");
+    eprintln!("       aoc run [DAY [PART]] [--input PATH|-] [--implementation NAME | --stream]");
+    eprintln!("               [--trace PATH|-] [--format FORMAT]");
+    eprintln!("       aoc bench [DAY] [--input PATH|-] [--iterations N] [--memory]");
+    eprintln!("                 [--format FORMAT]");
+    eprintln!("       aoc verify [DAY] [--input PATH|-] [--answers PATH] [--format FORMAT]");
+    eprintln!("       aoc check [DAY] [--input PATH|-] [--format FORMAT]");
+    eprintln!("       aoc examples [DAY] [--format FORMAT]");
+    eprintln!("       aoc report [DAY] [--input PATH|-] [--output PATH] [--iterations N]");
+    eprintln!("                  [--answers PATH]");
+    eprintln!("       aoc generate DAY [--seed N] [KNOB=VALUE ...]");
+    eprintln!("       aoc batch DIRECTORY [DAY] [--threads N] [--format FORMAT]");
+    eprintln!("       aoc repl DAY [--input PATH]");
+    eprintln!("       aoc new DAY [--year YYYY] [--title TITLE]");
+    eprintln!("       aoc watch DAY [--input PATH] [--interval MILLISECONDS]");
+    eprintln!();
+    eprintln!("FORMAT is one of 'text' (the default), 'json' or 'csv'.");
+    eprintln!();
+    eprintln!("Every command takes --year YYYY to select the days of a single year, and");
+    eprintln!("covers the days of every year without it. A DAY is one of the latest year");
+    eprintln!("unless --year is given, or can be given as YYYY/DAY, as in 2018/3. New days");
+    eprintln!("are created for the latest year unless --year is given.");
+    eprintln!();
+    eprintln!("The input of a day is read from PATH, from the standard input if PATH is");
+    eprintln!("'-', from $AOC_INPUT_DIR/YYYY/decNN.txt if the variable is set, or");
+    eprintln!("otherwise from the input directory of the day crate. The days of 2018 are");
+    eprintln!("also found in $AOC_INPUT_DIR/decNN.txt.");
+    eprintln!();
+    eprintln!("With --cache DIRECTORY the parsed inputs are saved in DIRECTORY, keyed by");
+    eprintln!("a hash of the input, and loaded from there by later runs.");
+    eprintln!();
+    eprintln!("With --memory the bench command also reports the peak resident set size");
+    eprintln!("of each phase and, if the runner is built with the count-allocations");
+    eprintln!("feature, the allocations, bytes allocated and peak heap usage.");
+    eprintln!();
+    eprintln!("With --trace the solutions explain how they reached their answers. The");
+    eprintln!("trace is printed to the standard error for '-' or saved as JSON lines.");
+    eprintln!();
+    eprintln!("The batch command runs every file in DIRECTORY/YYYY/decNN for each day,");
+    eprintln!("or DIRECTORY/decNN for the days of 2018, using as many threads as there");
+    eprintln!("are CPUs unless --threads is given.");
+    eprintln!();
+    eprintln!("The report command runs and times the days and writes a self-contained");
+    eprintln!("HTML page with their answers, timing charts and figures of their inputs");
+    eprintln!("to PATH, or report.html by default.");
+    eprintln!();
+    eprintln!("The examples command runs the examples from the puzzle descriptions in");
+    eprintln!("the examples directory of each day crate with every implementation.");
+    eprintln!();
+    eprintln!("The repl command parses the input of a day once and starts a shell for");
+    eprintln!("exploring it. Type 'help' in the shell to list its commands.");
+    eprintln!();
+    eprintln!("The watch command re-runs the examples and the input of a day whenever");
+    eprintln!("its sources or input change and compares the answers and timings with");
+    eprintln!("the previous run.");
+    eprintln!();
+    eprintln!("The new command creates the crate of a new day from a template and");
+    eprintln!("registers it with the workspace and the runner. TITLE is the title of");
+    eprintln!("the puzzle, which the info command shows with the rest of its description.");
+    eprintln!();
+    eprintln!("'aoc generate DAY --help' lists the knobs of the input generator of a day.");
+    process::exit(2);
+}
+
+// Remove the option with the given name and its value from `args` and return the value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let index = args.iter().position(|arg| arg == name)?;
+
+    if index + 1 >= args.len() {
+        eprintln!("Missing value for {}", name);
+        usage();
+    }
+
+    let value = args.remove(index + 1);
+    args.remove(index);
+
+    Some(value)
+}
+
+// Remove the flag with the given name from `args` and return whether it was there.
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    match args.iter().position(|arg| arg == name) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+// The latest year with any days. Its days are the ones we are working on, so a day given without
+// a year, such as "3", is one of them.
+fn latest_year(days: &[Day]) -> u32 {
+    let years = days::years(days);
+    years[years.len() - 1]
+}
+
+// Select the given day, or every day of the given year, or every day of every year if no year was
+// given.
+fn select_days<'a>(days: &'a [Day], year: Option<u32>, arg: Option<&String>) -> Vec<&'a Day> {
+    match arg {
+        Some(s) => match days::find(days, year.unwrap_or_else(|| latest_year(days)), s) {
+            Some(index) => vec![&days[index]],
+            None => {
+                eprintln!("Unknown day: {}", s);
+                usage()
+            }
+        },
+        None => days
+            .iter()
+            .filter(|day| year.is_none_or(|year| day.year() == year))
+            .collect(),
+    }
+}
+
+// Select both parts unless we have been asked for a specific one.
+fn select_parts(arg: Option<&String>) -> Vec<Part> {
+    match arg {
+        Some(s) => match s.parse::<Part>() {
+            Ok(part) => vec![part],
+            Err(_) => {
+                eprintln!("Unknown part: {}", s);
+                usage()
+            }
+        },
+        None => Part::all(),
+    }
+}
+
+// Call `f` for each of the given days with the input source of the day. We keep going if a day
+// fails, but return the exit code of the first failure.
+fn for_each_day<F>(days: &[&Day], input: Option<&str>, mut f: F) -> i32
+where
+    F: FnMut(&Day, &Source) -> Result<()>,
+{
+    if input.is_some() && days.len() != 1 {
+        eprintln!("An explicit input can only be given for a single day");
+        usage();
+    }
+
+    let mut exit_code = 0;
+
+    for day in days {
+        let source = day.source(input);
+        let (result, mut warnings) = normalize::capture(|| f(day, &source));
+
+        // Commands parsing the same input many times would otherwise repeat the warnings.
+        warnings.dedup();
+
+        for warning in warnings {
+            eprintln!("{}: {}: warning: {}", day.label(), source, warning);
+        }
+
+        if let Err(e) = result {
+            eprintln!("{}: {}: {}", day.label(), source, e);
+
+            if exit_code == 0 {
+                exit_code = e.exit_code();
+            }
+        }
+    }
+
+    exit_code
+}
+
+fn list(days: &[&Day]) {
+    for day in days {
+        println!(
+            "{} {:2} {}  {}",
+            day.year(),
+            day.number(),
+            day.name(),
+            day.puzzle().title
+        );
+    }
+}
+
+// Duration as a number of nanoseconds for use in records.
+fn nanos(d: Duration) -> i64 {
+    d.as_nanos() as i64
+}
+
+// Run the given parts of a day. Parts that have an implementation with the given name use it,
+// while every other part uses its default implementation.
+fn run(
+    day: &Day,
+    parts: &[Part],
+    implementation: Option<&str>,
+    source: &Source,
+    cache: Option<&Cache>,
+    output: &mut Output,
+    mut tracer: Option<&mut Tracer>,
+) -> Result<()> {
+    let data = source.read()?;
+    let input_hash = hash::hex(&data);
+
+    let (input, events) = trace::capture(|| day.load(&data, cache));
+    if let Some(tracer) = tracer.as_mut() {
+        tracer.write(day.year(), day.name(), "parse", &events)?;
+    }
+    let input = input?;
+
+    for part in parts {
+        let implementations = day.implementations(*part);
+        let index = implementation
+            .and_then(|name| implementations.iter().position(|i| *i == name))
+            .unwrap_or(0);
+
+        let start = Instant::now();
+        let (answer, events) = trace::capture(|| day.solve_with(&*input, *part, index));
+        let elapsed = start.elapsed();
+
+        if let Some(tracer) = tracer.as_mut() {
+            tracer.write(day.year(), day.name(), &format!("part {}", part), &events)?;
+        }
+        let answer = answer?;
+
+        output.emit(
+            &format!("{} part {}: {}", day.label(), part, answer),
+            Record::new()
+                .integer("year", day.year())
+                .string("day", day.name())
+                .integer("part", *part as i64)
+                .string("answer", answer)
+                .string("implementation", implementations[index])
+                .integer("elapsed_ns", nanos(elapsed))
+                .string("input_hash", input_hash.as_str()),
+        );
+    }
+
+    Ok(())
+}
+
+fn bench(
+    day: &Day,
+    source: &Source,
+    iterations: usize,
+    memory: bool,
+    cache: Option<&Cache>,
+    output: &mut Output,
+) -> Result<()> {
+    let data = source.read()?;
+    let input_hash = hash::hex(&data);
+
+    for timing in bench::run(day, &data, iterations, cache, memory)? {
+        let statistics = &timing.statistics;
+        let phase = if timing.implementation == "default" {
+            timing.phase.clone()
+        } else {
+            format!("{} ({})", timing.phase, timing.implementation)
+        };
+
+        let mut text = format!("{} {:<15}  {}", day.label(), phase, statistics);
+        let mut record = Record::new()
+            .integer("year", day.year())
+            .string("day", day.name())
+            .string("phase", timing.phase.as_str())
+            .string("implementation", timing.implementation)
+            .integer("iterations", iterations as i64)
+            .integer("min_ns", nanos(statistics.min()))
+            .integer("median_ns", nanos(statistics.median()))
+            .integer("mean_ns", nanos(statistics.mean()))
+            .integer("stddev_ns", nanos(statistics.stddev()));
+
+        // Only what could be measured is shown, which is the same for every phase.
+        if let Some(usage) = timing.memory {
+            if let (Some(allocations), Some(bytes)) = (usage.allocations, usage.bytes) {
+                text += &format!(
+                    "  allocs {:>9}  bytes {:>9}",
+                    allocations,
+                    memory::format_bytes(bytes)
+                );
+                record = record
+                    .integer("allocations", allocations as i64)
+                    .integer("allocated_bytes", bytes as i64);
+            }
+
+            if let Some(peak) = usage.peak_heap {
+                text += &format!("  heap {:>9}", memory::format_bytes(peak));
+                record = record.integer("peak_heap_bytes", peak as i64);
+            }
+
+            if let Some(peak) = usage.peak_rss {
+                text += &format!("  rss {:>9}", memory::format_bytes(peak));
+                record = record.integer("peak_rss_bytes", peak as i64);
+            }
+        }
+
+        output.emit(&text, record.string("input_hash", input_hash.as_str()));
+    }
+
+    Ok(())
+}
+
+// Compare the answers of the given day against the known answers. Every mismatch is counted in
+// `mismatches`.
+fn verify(
+    day: &Day,
+    source: &Source,
+    answers: &Answers,
+    mismatches: &mut usize,
+    cache: Option<&Cache>,
+    output: &mut Output,
+) -> Result<()> {
+    let data = source.read()?;
+    let input_hash = hash::hex(&data);
+    let input = day.load(&data, cache)?;
+
+    for part in Part::all() {
+        let answer = day.solve(&*input, part)?;
+        let expected = answers.get(day.year(), day.name(), part);
+
+        let (status, text) = match expected {
+            Some(expected) if expected == answer => ("ok", format!("ok ({})", answer)),
+            Some(expected) => {
+                *mismatches += 1;
+                (
+                    "mismatch",
+                    format!("MISMATCH (expected {}, got {})", expected, answer),
+                )
+            }
+            None => ("unknown", format!("unknown ({})", answer)),
+        };
+
+        output.emit(
+            &format!("{} part {}: {}", day.label(), part, text),
+            Record::new()
+                .integer("year", day.year())
+                .string("day", day.name())
+                .integer("part", part as i64)
+                .string("answer", answer)
+                .string("expected", expected.unwrap_or(""))
+                .string("status", status)
+                .string("input_hash", input_hash.as_str()),
+        );
+    }
+
+    Ok(())
+}
+
+// Run the given parts of a day in a single pass over the input without parsing all of it first.
+// Parts that need the whole input are skipped.
+fn stream(day: &Day, parts: &[Part], source: &Source, output: &mut Output) -> Result<()> {
+    let streaming_parts = day.streaming_parts();
+
+    for part in parts {
+        if !streaming_parts.contains(part) {
+            eprintln!(
+                "{} part {}: cannot be streamed, skipping",
+                day.label(),
+                part
+            );
+            continue;
+        }
+
+        let mut reader = BufReader::new(HashingReader::new(source.open()?));
+
+        let start = Instant::now();
+        let answer = day.stream(&mut reader, *part)?;
+        let elapsed = start.elapsed();
+
+        output.emit(
+            &format!("{} part {}: {}", day.label(), part, answer),
+            Record::new()
+                .integer("year", day.year())
+                .string("day", day.name())
+                .integer("part", *part as i64)
+                .string("answer", answer)
+                .string("implementation", "streaming")
+                .integer("elapsed_ns", nanos(elapsed))
+                .string("input_hash", reader.get_ref().hasher().hex()),
+        );
+    }
+
+    Ok(())
+}
+
+// Run every implementation of each part of the given day and compare their answers against the
+// answer of the default implementation. Every disagreement is counted in `disagreements`.
+fn check(
+    day: &Day,
+    source: &Source,
+    disagreements: &mut usize,
+    cache: Option<&Cache>,
+    output: &mut Output,
+) -> Result<()> {
+    let data = source.read()?;
+    let input_hash = hash::hex(&data);
+    let input = day.load(&data, cache)?;
+
+    for part in Part::all() {
+        let implementations = day.implementations(part);
+        let expected = day.solve(&*input, part)?;
+
+        for (index, implementation) in implementations.iter().enumerate() {
+            let answer = day.solve_with(&*input, part, index)?;
+
+            let (status, text) = if answer == expected {
+                ("ok", format!("ok ({})", answer))
+            } else {
+                *disagreements += 1;
+                (
+                    "disagreement",
+                    format!(
+                        "DISAGREEMENT (default {} gave {}, got {})",
+                        implementations[0], expected, answer
+                    ),
+                )
+            };
+
+            output.emit(
+                &format!(
+                    "{} part {} ({}): {}",
+                    day.label(),
+                    part,
+                    implementation,
+                    text
+                ),
+                Record::new()
+                    .integer("year", day.year())
+                    .string("day", day.name())
+                    .integer("part", part as i64)
+                    .string("implementation", *implementation)
+                    .string("answer", answer)
+                    .string("default", expected.as_str())
+                    .string("status", status)
+                    .string("input_hash", input_hash.as_str()),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// Run the examples from the puzzle descriptions of the given days with every implementation and
+// print every example that does not give the expected answer. Returns 1 if any example failed, or
+// the exit code of the first day whose examples could not be run.
+fn examples(days: &[&Day], output: &mut Output) -> i32 {
+    let root = root();
+    let mut exit_code = 0;
+
+    for day in days {
+        let directory = root.join("src").join(day.directory()).join("examples");
+        let name = day.label();
+
+        let failures = match day.examples(&directory) {
+            Ok(failures) => failures,
+            Err(e) => {
+                eprintln!("{}: {}", name, e);
+
+                if exit_code == 0 {
+                    exit_code = e.exit_code();
+                }
+                continue;
+            }
+        };
+
+        let record = || {
+            Record::new()
+                .integer("year", day.year())
+                .string("day", day.name())
+        };
+
+        if failures.is_empty() {
+            output.emit(
+                &format!("{} examples: ok", name),
+                record().string("status", "ok").string("failure", ""),
+            );
+        }
+
+        for failure in failures {
+            output.emit(
+                &format!("{} {}", name, failure),
+                record()
+                    .string("status", "failed")
+                    .string("failure", failure),
+            );
+
+            if exit_code == 0 {
+                exit_code = 1;
+            }
+        }
+    }
+
+    exit_code
+}
+
+// Run every input of the given days found in the given directory on `threads` threads and print
+// a table of the answers. Returns the exit code of the first failing input.
+fn batch(
+    directory: &str,
+    days: &[&Day],
+    threads: usize,
+    cache: Option<&Cache>,
+    output: &mut Output,
+) -> i32 {
+    let jobs = match batch::discover(Path::new(directory), days) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            eprintln!("{}: {}", directory, e);
+            return e.exit_code();
+        }
+    };
+
+    if jobs.is_empty() {
+        eprintln!("No inputs found in {}", directory);
+        return 0;
+    }
+
+    let results = batch::run(&jobs, threads, cache);
+
+    let names: Vec<String> = jobs
+        .iter()
+        .map(|job| job.path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    let width = names
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+    let answer_width = |part: usize| {
+        results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(|answers| answers.parts[part].0.len())
+            .max()
+            .unwrap_or(0)
+            .max(6)
+    };
+    let (width1, width2) = (answer_width(0), answer_width(1));
+
+    output.text(&format!(
+        "{:<10}  {:<width$}  {:<width1$}  {:<width2$}  {:>10}  {:>10}  {:>10}",
+        "day", "input", "part 1", "part 2", "parse", "part 1", "part 2",
+    ));
+
+    let mut exit_code = 0;
+    let mut failures = 0;
+
+    for ((job, name), result) in jobs.iter().zip(names.iter()).zip(results.iter()) {
+        let record = Record::new()
+            .integer("year", job.day.year())
+            .string("day", job.day.name())
+            .string("input", name.as_str());
+
+        match result {
+            Ok(answers) => {
+                for warning in answers.warnings.iter() {
+                    eprintln!(
+                        "{}: {}: warning: {}",
+                        job.day.label(),
+                        job.path.display(),
+                        warning
+                    );
+                }
+
+                let (answer1, time1) = &answers.parts[0];
+                let (answer2, time2) = &answers.parts[1];
+
+                output.emit(
+                    &format!(
+                        "{:<10}  {:<width$}  {:<width1$}  {:<width2$}  {:>10}  {:>10}  {:>10}",
+                        job.day.label(),
+                        name,
+                        answer1,
+                        answer2,
+                        bench::format_duration(answers.parse),
+                        bench::format_duration(*time1),
+                        bench::format_duration(*time2),
+                    ),
+                    record
+                        .string("status", "ok")
+                        .string("part1", answer1.as_str())
+                        .string("part2", answer2.as_str())
+                        .integer("parse_ns", nanos(answers.parse))
+                        .integer("part1_ns", nanos(*time1))
+                        .integer("part2_ns", nanos(*time2))
+                        .string("input_hash", answers.input_hash.as_str())
+                        .string("error", ""),
+                );
+            }
+            Err(e) => {
+                failures += 1;
+
+                if exit_code == 0 {
+                    exit_code = e.exit_code();
+                }
+
+                output.emit(
+                    &format!("{:<10}  {:<width$}  error: {}", job.day.label(), name, e),
+                    record
+                        .string("status", "error")
+                        .string("part1", "")
+                        .string("part2", "")
+                        .integer("parse_ns", 0)
+                        .integer("part1_ns", 0)
+                        .integer("part2_ns", 0)
+                        .string("input_hash", "")
+                        .string("error", e.to_string()),
+                );
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{} of {} input(s) failed", failures, jobs.len());
+    }
+
+    exit_code
+}
+
+// Describe the puzzles of the given days.
+fn info(days: &[&Day], output: &mut Output) {
+    for day in days {
+        let puzzle = day.puzzle();
+        let mut text = format!("{}: {}", day.label(), puzzle.title);
+
+        for part in Part::all() {
+            text.push_str(&format!(
+                "\n  part {}: {} ({})",
+                part,
+                puzzle.part(part),
+                puzzle.answer(part)
+            ));
+        }
+
+        text.push_str(&format!("\n  input: {}", puzzle.input));
+
+        output.emit(
+            &text,
+            Record::new()
+                .integer("year", day.year())
+                .string("day", day.name())
+                .integer("number", day.number())
+                .string("title", puzzle.title)
+                .string("part1", puzzle.part(Part::One))
+                .string("part1_answer", puzzle.answer(Part::One).to_string())
+                .string("part2", puzzle.part(Part::Two))
+                .string("part2_answer", puzzle.answer(Part::Two).to_string())
+                .string("input", puzzle.input),
+        );
+    }
+}
+
+// Load the known answers from the given file or exit.
+fn load_answers(path: &str) -> Answers {
+    match Answers::load(path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            process::exit(e.exit_code());
+        }
+    }
+}
+
+fn knobs_help(day: &Day) {
+    eprintln!("Knobs of {}:", day.label());
+
+    for (name, description) in day.knobs() {
+        eprintln!("  {:<10} {}", name, description);
+    }
+}
+
+// Write a synthetic input for the given day to the standard output.
+fn generate(day: &Day, seed: u64, args: &[String]) -> Result<()> {
+    let knobs = Knobs::parse(args)?;
+    let known: Vec<&str> = day.knobs().iter().map(|(name, _)| *name).collect();
+
+    if let Some(name) = knobs.names().iter().find(|name| !known.contains(name)) {
+        eprintln!("Unknown knob: {}", name);
+        knobs_help(day);
+        process::exit(2);
+    }
+
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+
+    day.generate(&mut Rng::new(seed), &knobs, &mut writer)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+// The root of the repository the runner was built from.
+fn root() -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    root.canonicalize().unwrap_or(root)
+}
+
+// Create the crate of a new day in the repository the runner was built from.
+fn new(year: u32, number: u32, title: &str) -> i32 {
+    let root = root();
+    let directory = days::directory(year, &format!("dec{:02}", number));
+
+    match scaffold::create(&root, year, number, title) {
+        Ok(paths) => {
+            for path in paths {
+                let path = path.strip_prefix(&root).unwrap_or(&path);
+                println!("{}", path.display());
+            }
+
+            println!();
+            println!(
+                "Put the input in src/{}/input/data.txt and the example",
+                directory
+            );
+            println!(
+                "from the puzzle in src/{}/examples, and describe the puzzle",
+                directory
+            );
+            println!(
+                "in src/{}/src/metadata.rs. Once it is solved, record",
+                directory
+            );
+            println!("the answers in answers.txt, which verify checks them against.");
+            0
+        }
+        Err(e) => {
+            eprintln!("{}/dec{:02}: {}", year, number, e);
+            e.exit_code()
+        }
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let days = days::all();
+
+    let input = take_option(&mut args, "--input");
+    let answers = take_option(&mut args, "--answers");
+    let title = take_option(&mut args, "--title");
+    let report_path = take_option(&mut args, "--output");
+    let implementation = take_option(&mut args, "--implementation");
+    let streaming = take_flag(&mut args, "--stream");
+    let memory = take_flag(&mut args, "--memory");
+    let cache = take_option(&mut args, "--cache").map(Cache::new);
+    let mut tracer = match take_option(&mut args, "--trace") {
+        Some(target) => match Tracer::open(&target) {
+            Ok(tracer) => Some(tracer),
+            Err(e) => {
+                eprintln!("{}: {}", target, e);
+                process::exit(3);
+            }
+        },
+        None => None,
+    };
+    let iterations = match take_option(&mut args, "--iterations") {
+        Some(s) => match s.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                eprintln!("Invalid number of iterations: {}", s);
+                usage()
+            }
+        },
+        None => DEFAULT_ITERATIONS,
+    };
+    let seed = match take_option(&mut args, "--seed") {
+        Some(s) => match s.parse::<u64>() {
+            Ok(seed) => seed,
+            Err(_) => {
+                eprintln!("Invalid seed: {}", s);
+                usage()
+            }
+        },
+        None => DEFAULT_SEED,
+    };
+    let threads = match take_option(&mut args, "--threads") {
+        Some(s) => match s.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                eprintln!("Invalid number of threads: {}", s);
+                usage()
+            }
+        },
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let interval = match take_option(&mut args, "--interval") {
+        Some(s) => match s.parse::<u64>() {
+            Ok(n) if n > 0 => Duration::from_millis(n),
+            _ => {
+                eprintln!("Invalid interval: {}", s);
+                usage()
+            }
+        },
+        None => Duration::from_millis(DEFAULT_INTERVAL),
+    };
+    let mut output = match take_option(&mut args, "--format") {
+        Some(s) => match s.parse::<Format>() {
+            Ok(format) => Output::new(format),
+            Err(_) => {
+                eprintln!("Unknown format: {}", s);
+                usage()
+            }
+        },
+        None => Output::new(Format::Text),
+    };
+    let year = match take_option(&mut args, "--year") {
+        Some(s) => match scaffold::parse_year(&s) {
+            Some(year) => Some(year),
+            None => {
+                eprintln!("Invalid year: {}", s);
+                usage()
+            }
+        },
+        None => None,
+    };
+
+    // Only a new day can be created for a year that does not have any yet.
+    if let Some(year) = year {
+        if !days::years(&days).contains(&year) && args.first().map(|s| s.as_str()) != Some("new") {
+            eprintln!("Unknown year: {}", year);
+            usage();
+        }
+    }
+
+    let exit_code = match args.first().map(|s| s.as_str()) {
+        Some("list") => {
+            list(&select_days(&days, year, None));
+            0
+        }
+        Some("info") => {
+            info(&select_days(&days, year, args.get(1)), &mut output);
+            0
+        }
+        Some("run") => {
+            let selected = select_days(&days, year, args.get(1));
+            let parts = select_parts(args.get(2));
+
+            if let Some(name) = implementation.as_ref() {
+                let known = selected.iter().any(|day| {
+                    parts
+                        .iter()
+                        .any(|p| day.implementations(*p).contains(&&**name))
+                });
+
+                if !known {
+                    eprintln!("Unknown implementation: {}", name);
+                    usage();
+                }
+            }
+
+            if streaming {
+                if implementation.is_some() {
+                    eprintln!("--implementation cannot be combined with --stream");
+                    usage();
+                }
+
+                let streamable = selected.iter().any(|day| {
+                    let streaming_parts = day.streaming_parts();
+                    parts.iter().any(|p| streaming_parts.contains(p))
+                });
+
+                if !streamable {
+                    eprintln!("None of the selected parts can be streamed");
+                    usage();
+                }
+
+                for_each_day(&selected, input.as_deref(), |day, source| {
+                    stream(day, &parts, source, &mut output)
+                })
+            } else {
+                for_each_day(&selected, input.as_deref(), |day, source| {
+                    run(
+                        day,
+                        &parts,
+                        implementation.as_deref(),
+                        source,
+                        cache.as_ref(),
+                        &mut output,
+                        tracer.as_mut(),
+                    )
+                })
+            }
+        }
+        Some("bench") => {
+            let selected = select_days(&days, year, args.get(1));
+
+            if memory && !memory::COUNTING {
+                eprintln!("Allocations are only counted if the runner is built with");
+                eprintln!("--features count-allocations");
+            }
+
+            for_each_day(&selected, input.as_deref(), |day, source| {
+                bench(day, source, iterations, memory, cache.as_ref(), &mut output)
+            })
+        }
+        Some("verify") => {
+            let selected = select_days(&days, year, args.get(1));
+            let path = answers.unwrap_or_else(|| String::from(answers::ANSWERS));
+            let answers = load_answers(&path);
+
+            let mut mismatches = 0;
+            let exit_code = for_each_day(&selected, input.as_deref(), |day, source| {
+                verify(
+                    day,
+                    source,
+                    &answers,
+                    &mut mismatches,
+                    cache.as_ref(),
+                    &mut output,
+                )
+            });
+
+            if mismatches > 0 {
+                eprintln!("{} answer(s) did not match {}", mismatches, path);
+            }
+
+            match exit_code {
+                0 if mismatches > 0 => 1,
+                _ => exit_code,
+            }
+        }
+        Some("report") => {
+            let selected = select_days(&days, year, args.get(1));
+            let answers = load_answers(answers.as_deref().unwrap_or(answers::ANSWERS));
+            let path = report_path.unwrap_or_else(|| String::from(DEFAULT_REPORT));
+
+            let mut report = report::Report::new(iterations);
+            let exit_code = for_each_day(&selected, input.as_deref(), |day, source| {
+                report.add(day, &source.read()?, &answers, cache.as_ref())
+            });
+
+            match fs::write(&path, report.html()) {
+                Ok(()) => {
+                    println!("Wrote the report of {} day(s) to {}", selected.len(), path);
+                    exit_code
+                }
+                Err(e) => {
+                    eprintln!("{}: {}", path, e);
+                    3
+                }
+            }
+        }
+        Some("check") => {
+            let selected = select_days(&days, year, args.get(1));
+
+            let mut disagreements = 0;
+            let exit_code = for_each_day(&selected, input.as_deref(), |day, source| {
+                check(day, source, &mut disagreements, cache.as_ref(), &mut output)
+            });
+
+            if disagreements > 0 {
+                eprintln!("{} implementation(s) disagreed", disagreements);
+            }
+
+            match exit_code {
+                0 if disagreements > 0 => 1,
+                _ => exit_code,
+            }
+        }
+        Some("examples") => {
+            let selected = select_days(&days, year, args.get(1));
+
+            examples(&selected, &mut output)
+        }
+        Some("generate") => {
+            let day = match args.get(1) {
+                Some(_) => select_days(&days, year, args.get(1))[0],
+                None => usage(),
+            };
+
+            if args[2..].iter().any(|arg| arg == "--help") {
+                knobs_help(day);
+                0
+            } else {
+                match generate(day, seed, &args[2..]) {
+                    Ok(()) => 0,
+                    Err(e) => {
+                        eprintln!("{}: {}", day.label(), e);
+                        e.exit_code()
+                    }
+                }
+            }
+        }
+        Some("batch") => {
+            let directory = match args.get(1) {
+                Some(directory) => directory,
+                None => usage(),
+            };
+            let selected = select_days(&days, year, args.get(2));
+
+            batch(directory, &selected, threads, cache.as_ref(), &mut output)
+        }
+        Some("repl") => {
+            let day = match args.get(1) {
+                Some(_) => select_days(&days, year, args.get(1))[0],
+                None => usage(),
+            };
+
+            for_each_day(&[day], input.as_deref(), |day, source| {
+                repl::run(&repl::Session::load(day, source, cache.as_ref())?)
+            })
+        }
+        Some("new") => {
+            let number = match args.get(1) {
+                Some(s) => match scaffold::parse_day(s) {
+                    Some(number) => number,
+                    None => {
+                        eprintln!("Invalid day: {}", s);
+                        usage()
+                    }
+                },
+                None => usage(),
+            };
+
+            let title = title.unwrap_or_else(|| format!("Day {}", number));
+
+            new(year.unwrap_or_else(|| latest_year(&days)), number, &title)
+        }
+        Some("watch") => {
+            let day = match args.get(1) {
+                Some(_) => select_days(&days, year, args.get(1))[0],
+                None => usage(),
+            };
+
+            let path = match day.source(input.as_deref()) {
+                Source::Path(path) => path,
+                Source::Stdin => {
+                    eprintln!("The standard input cannot be watched");
+                    usage()
+                }
+            };
+            let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+
+            watch::Watcher::new(&root(), &cargo, day, input.as_deref(), &path, interval).run()
+        }
+        _ => usage(),
+    };
+
+    output.finish();
+
+    if let Some(tracer) = tracer {
+        if let Err(e) = tracer.finish() {
+            eprintln!("Failed to save the trace: {}", e);
+            process::exit(3);
+        }
+    }
+
+    process::exit(exit_code);
+}
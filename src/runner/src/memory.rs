@@ -0,0 +1,222 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Memory accounting of the phases of a day.
+//
+// With the `count-allocations` feature the runner installs a global allocator that counts the
+// allocations, the bytes allocated and the peak number of bytes in use. Independently of the
+// feature, the peak resident set size of the process is sampled from `/proc/self/status` on
+// Linux, where writing 5 to `/proc/self/clear_refs` resets it before each phase.
+
+use std::fs;
+
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    pub static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    pub static BYTES: AtomicU64 = AtomicU64::new(0);
+    pub static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    pub static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    // The system allocator with counters on top. A reallocation counts as an allocation of the
+    // new size.
+    pub struct Counting;
+
+    impl Counting {
+        fn allocated(&self, size: usize) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(size as u64, Ordering::Relaxed);
+
+            let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let pointer = System.alloc(layout);
+
+            if !pointer.is_null() {
+                self.allocated(layout.size());
+            }
+
+            pointer
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let pointer = System.alloc_zeroed(layout);
+
+            if !pointer.is_null() {
+                self.allocated(layout.size());
+            }
+
+            pointer
+        }
+
+        unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+            System.dealloc(pointer, layout);
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+
+        unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, size: usize) -> *mut u8 {
+            let result = System.realloc(pointer, layout, size);
+
+            if !result.is_null() {
+                CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+                self.allocated(size);
+            }
+
+            result
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+}
+
+// Whether the runner was built with the counting allocator.
+pub const COUNTING: bool = cfg!(feature = "count-allocations");
+
+// The memory used by a single run of a phase. Every field is only known if it could be measured.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Usage {
+    // Number of allocations and the total number of bytes they requested.
+    pub allocations: Option<u64>,
+    pub bytes: Option<u64>,
+
+    // The largest number of bytes in use at once by allocations made during the run.
+    pub peak_heap: Option<u64>,
+
+    // The peak resident set size of the process during the run.
+    pub peak_rss: Option<u64>,
+}
+
+// The counters of the allocator: allocations, bytes and bytes in use. The peak is reset to the
+// bytes in use, such that it only covers what follows.
+#[cfg(feature = "count-allocations")]
+fn counters() -> Option<(u64, u64, usize)> {
+    use std::sync::atomic::Ordering;
+
+    use self::counting::{ALLOCATIONS, BYTES, CURRENT, PEAK};
+
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+
+    Some((
+        ALLOCATIONS.load(Ordering::Relaxed),
+        BYTES.load(Ordering::Relaxed),
+        current,
+    ))
+}
+
+#[cfg(not(feature = "count-allocations"))]
+fn counters() -> Option<(u64, u64, usize)> {
+    None
+}
+
+#[cfg(feature = "count-allocations")]
+fn peak() -> usize {
+    counting::PEAK.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+#[cfg(not(feature = "count-allocations"))]
+fn peak() -> usize {
+    0
+}
+
+// Read the peak resident set size in bytes from the contents of `/proc/self/status`.
+fn parse_status(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes = line["VmHWM:".len()..].trim().strip_suffix("kB")?;
+
+    kilobytes.trim().parse::<u64>().ok().map(|n| n * 1024)
+}
+
+// Reset the peak resident set size to the current one. Returns false if that is not supported.
+fn reset_rss() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+fn peak_rss() -> Option<u64> {
+    parse_status(&fs::read_to_string("/proc/self/status").ok()?)
+}
+
+// Run the given closure and measure its memory usage. The result of the closure is dropped after
+// the measurement, so freeing it does not count.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Usage) {
+    let rss = reset_rss();
+    let start = counters();
+
+    let result = f();
+
+    let mut usage = Usage::default();
+
+    if let Some((allocations, bytes, current)) = start {
+        let peak = peak();
+        let (end_allocations, end_bytes, _) = counters().unwrap_or_default();
+
+        usage.allocations = Some(end_allocations - allocations);
+        usage.bytes = Some(end_bytes - bytes);
+        usage.peak_heap = Some(peak.saturating_sub(current) as u64);
+    }
+
+    if rss {
+        usage.peak_rss = peak_rss();
+    }
+
+    (result, usage)
+}
+
+// Format a number of bytes using the largest binary unit that keeps the value above 1.
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1 << 10 {
+        format!("{}B", bytes)
+    } else if bytes < 1 << 20 {
+        format!("{:.1}KiB", bytes as f64 / f64::from(1 << 10))
+    } else if bytes < 1 << 30 {
+        format!("{:.1}MiB", bytes as f64 / f64::from(1 << 20))
+    } else {
+        format!("{:.1}GiB", bytes as f64 / f64::from(1 << 30))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_peak_rss() {
+        let status = "Name:\taoc\nVmPeak:\t   10000 kB\nVmHWM:\t    1820 kB\nVmRSS:\t  1000 kB\n";
+
+        assert_eq!(parse_status(status), Some(1820 * 1024));
+        assert_eq!(parse_status("Name:\taoc\n"), None);
+        assert_eq!(parse_status("VmHWM:\tlots\n"), None);
+    }
+
+    #[test]
+    fn format_sizes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0MiB");
+        assert_eq!(format_bytes(5 << 30), "5.0GiB");
+    }
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn count_allocations() {
+        let (_, usage) = measure(|| {
+            let first = vec![0u8; 1000];
+            drop(first);
+            vec![0u8; 600]
+        });
+
+        assert!(usage.allocations.unwrap() >= 2);
+        assert!(usage.bytes.unwrap() >= 1600);
+
+        // Other tests allocate and free concurrently, so the peak is only known to be there.
+        assert!(usage.peak_heap.is_some());
+    }
+}
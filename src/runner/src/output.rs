@@ -0,0 +1,210 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    // Human readable lines.
+    Text,
+
+    // A JSON array with one object per record.
+    Json,
+
+    // Comma separated values with a header line.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Format, ()> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+}
+
+// A single machine readable record. The order of the fields is kept in the output.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Record {
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl Record {
+    pub fn new() -> Record {
+        Record { fields: Vec::new() }
+    }
+
+    pub fn string<S: Into<String>>(mut self, key: &'static str, value: S) -> Record {
+        self.fields.push((key, Value::String(value.into())));
+        self
+    }
+
+    pub fn integer<I: Into<i64>>(mut self, key: &'static str, value: I) -> Record {
+        self.fields.push((key, Value::Integer(value.into())));
+        self
+    }
+}
+
+// Collects the output of a command. Text is printed as we go, while the machine readable formats
+// are buffered and printed as a whole by `finish()`.
+pub struct Output {
+    format: Format,
+    records: Vec<Record>,
+}
+
+impl Output {
+    pub fn new(format: Format) -> Output {
+        Output {
+            format,
+            records: Vec::new(),
+        }
+    }
+
+    // Emit a result. `text` is used for the text format and `record` for everything else.
+    pub fn emit(&mut self, text: &str, record: Record) {
+        match self.format {
+            Format::Text => println!("{}", text),
+            _ => self.records.push(record),
+        }
+    }
+
+    // Print a line that only belongs to the text format, such as the header of a table.
+    pub fn text(&mut self, text: &str) {
+        if self.format == Format::Text {
+            println!("{}", text);
+        }
+    }
+
+    pub fn finish(self) {
+        match self.format {
+            Format::Text => (),
+            Format::Json => println!("{}", json(&self.records)),
+            Format::Csv => print!("{}", csv(&self.records)),
+        }
+    }
+}
+
+fn json(records: &[Record]) -> String {
+    let objects: Vec<String> = records.iter().map(json_object).collect();
+
+    format!("[{}]", objects.join(",\n "))
+}
+
+// A single record as a JSON object.
+pub fn json_object(record: &Record) -> String {
+    let fields: Vec<String> = record
+        .fields
+        .iter()
+        .map(|(key, value)| match value {
+            Value::String(s) => format!("{}:{}", json_string(key), json_string(s)),
+            Value::Integer(i) => format!("{}:{}", json_string(key), i),
+        })
+        .collect();
+
+    format!("{{{}}}", fields.join(","))
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+// Every record is expected to have the same fields as the first record.
+fn csv(records: &[Record]) -> String {
+    let mut result = String::new();
+
+    if let Some(first) = records.first() {
+        let header: Vec<&str> = first.fields.iter().map(|(key, _)| *key).collect();
+        result.push_str(&header.join(","));
+        result.push('\n');
+    }
+
+    for record in records {
+        let values: Vec<String> = record
+            .fields
+            .iter()
+            .map(|(_, value)| match value {
+                Value::String(s) => csv_string(s),
+                Value::Integer(i) => i.to_string(),
+            })
+            .collect();
+
+        result.push_str(&values.join(","));
+        result.push('\n');
+    }
+
+    result
+}
+
+fn csv_string(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::new()
+                .string("day", "dec01")
+                .integer("part", 1)
+                .string("answer", "587"),
+            Record::new()
+                .string("day", "dec02")
+                .integer("part", 2)
+                .string("answer", "a \"quoted\", value"),
+        ]
+    }
+
+    #[test]
+    fn json_output() {
+        assert_eq!(
+            json(&records()),
+            "[{\"day\":\"dec01\",\"part\":1,\"answer\":\"587\"},\n \
+             {\"day\":\"dec02\",\"part\":2,\"answer\":\"a \\\"quoted\\\", value\"}]"
+        );
+        assert_eq!(json(&[]), "[]");
+        assert_eq!(json_string("a\nb\u{1}"), "\"a\\nb\\u0001\"");
+    }
+
+    #[test]
+    fn csv_output() {
+        assert_eq!(
+            csv(&records()),
+            "day,part,answer\ndec01,1,587\ndec02,2,\"a \"\"quoted\"\", value\"\n"
+        );
+        assert_eq!(csv(&[]), "");
+    }
+}
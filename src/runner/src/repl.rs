@@ -0,0 +1,229 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// An interactive shell for exploring the parsed input of a day.
+//
+// The input is parsed once when the shell starts. Every line is then either one of the commands
+// below, available for every day, or one of the commands the day offers for its own data
+// structures. Command names can be completed with the tab key.
+
+use std::any::Any;
+
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use common::cache::Cache;
+use common::input::Source;
+use common::{trace, Error, Part, Result};
+
+use days::Day;
+
+// The commands available for every day as pairs of usage and description.
+const BUILTIN: &[(&str, &str)] = &[
+    ("part1", "the answer of part 1 and how it was reached"),
+    ("part2", "the answer of part 2 and how it was reached"),
+    ("help", "list the commands"),
+    ("quit", "leave the shell"),
+];
+
+// What to do after a line has been executed.
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Print(String),
+    Quit,
+}
+
+// A parsed input together with the day it belongs to.
+pub struct Session<'a> {
+    day: &'a Day,
+    input: Box<dyn Any>,
+}
+
+impl<'a> Session<'a> {
+    pub fn load(day: &'a Day, source: &Source, cache: Option<&Cache>) -> Result<Session<'a>> {
+        let data = source.read()?;
+        let input = day.load(&data, cache)?;
+
+        Ok(Session { day, input })
+    }
+
+    // The usage and description of every command, built-in commands last.
+    pub fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        let mut commands = self.day.commands();
+        commands.extend_from_slice(BUILTIN);
+        commands
+    }
+
+    // The names of every command.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.commands()
+            .iter()
+            .filter_map(|(usage, _)| usage.split_whitespace().next())
+            .collect()
+    }
+
+    fn help(&self) -> String {
+        let lines: Vec<String> = self
+            .commands()
+            .iter()
+            .map(|(usage, description)| format!("{:<16} {}", usage, description))
+            .collect();
+
+        lines.join("\n")
+    }
+
+    // Solve the given part and show the trace events next to the answer.
+    fn solve(&self, part: Part) -> Result<String> {
+        let (answer, events) = trace::capture(|| self.day.solve(&*self.input, part));
+        let mut lines: Vec<String> = events.iter().map(|event| event.to_string()).collect();
+        lines.push(answer?);
+
+        Ok(lines.join("\n"))
+    }
+
+    // Execute a single line.
+    pub fn execute(&self, line: &str) -> Result<Action> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (*command, args),
+            None => return Ok(Action::Print(String::new())),
+        };
+
+        let output = match command {
+            "part1" => self.solve(Part::One)?,
+            "part2" => self.solve(Part::Two)?,
+            "help" => self.help(),
+            "quit" | "exit" => return Ok(Action::Quit),
+            _ => self.day.execute(&*self.input, command, args)?,
+        };
+
+        Ok(Action::Print(output))
+    }
+}
+
+// Complete the command name under the cursor. Only the first word of a line is completed, as the
+// arguments are all numbers or single characters. Returns the start of the word and the
+// candidates.
+pub fn complete(names: &[&str], line: &str, position: usize) -> (usize, Vec<String>) {
+    let before = &line[..position];
+    let start = before.len() - before.trim_start().len();
+    let word = &before[start..];
+
+    if word.contains(char::is_whitespace) {
+        return (position, Vec::new());
+    }
+
+    let candidates = names
+        .iter()
+        .filter(|name| name.starts_with(word))
+        .map(|name| name.to_string())
+        .collect();
+
+    (start, candidates)
+}
+
+struct Completion {
+    names: Vec<&'static str>,
+}
+
+impl Completer for Completion {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        position: usize,
+        _context: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete(&self.names, line, position))
+    }
+}
+
+impl Hinter for Completion {
+    type Hint = String;
+}
+
+impl Highlighter for Completion {}
+
+impl Validator for Completion {}
+
+impl Helper for Completion {}
+
+// Read lines from the terminal and execute them until the user quits. Errors of single commands
+// are shown without leaving the shell.
+pub fn run(session: &Session) -> Result<()> {
+    let mut editor = Editor::new().map_err(|e| Error::invalid(e.to_string()))?;
+    editor.set_helper(Some(Completion {
+        names: session.names(),
+    }));
+
+    let prompt = format!("{}> ", session.day.label());
+
+    loop {
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => return Ok(()),
+            Err(e) => return Err(Error::invalid(e.to_string())),
+        };
+
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+
+        match session.execute(&line) {
+            Ok(Action::Print(output)) => {
+                if !output.is_empty() {
+                    println!("{}", output);
+                }
+            }
+            Ok(Action::Quit) => return Ok(()),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use days;
+
+    #[test]
+    fn complete_command_names() {
+        let names = ["count", "intact", "at", "help"];
+
+        assert_eq!(complete(&names, "co", 2), (0, vec!["count".to_string()]));
+        assert_eq!(complete(&names, "  i", 3), (2, vec!["intact".to_string()]));
+        assert_eq!(complete(&names, "", 0).1.len(), 4);
+        assert_eq!(complete(&names, "at 1", 4), (4, Vec::new()));
+    }
+
+    #[test]
+    fn execute_lines() {
+        let all = days::all();
+        let day = &all[days::find(&all, 2018, "dec03").unwrap()];
+        let source = Source::Path(PathBuf::from(day.input()));
+        let session = Session::load(day, &source, None).unwrap();
+
+        assert!(session.names().contains(&"intact"));
+        assert!(session.names().contains(&"part1"));
+        assert_eq!(session.execute("").unwrap(), Action::Print(String::new()));
+        assert_eq!(session.execute("quit").unwrap(), Action::Quit);
+
+        match session.execute("part2").unwrap() {
+            Action::Print(output) => assert!(output.starts_with("intact claim id=")),
+            Action::Quit => panic!("expected output"),
+        }
+
+        assert!(session.execute("frobnicate").is_err());
+    }
+}
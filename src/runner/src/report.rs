@@ -0,0 +1,425 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// The HTML report of the days.
+//
+// `aoc report` runs the days like `verify` and `bench` do and writes a single HTML file that
+// summarises them: the answers compared with the known ones, a chart of the timings of every
+// phase and implementation, and the figures the days draw of their inputs. The styles and the
+// figures are embedded in the file, so it can be opened in a browser without a web server.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use common::cache::Cache;
+use common::hash;
+use common::metadata::Puzzle;
+use common::visualize::{escape, Figure, Svg};
+use common::{Part, Result};
+
+use answers::Answers;
+use bench::{self, format_duration, Timing};
+use days::Day;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em auto; max-width: 60em; color: #222222; }
+h1, h2 { font-weight: normal; }
+section { border-top: 1px solid #cccccc; margin-top: 2em; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { padding: 0.2em 0.8em; text-align: left; vertical-align: top; }
+th { border-bottom: 1px solid #cccccc; }
+td.answer { font-family: monospace; }
+.ok { color: #1a7f37; }
+.wrong, .error { color: #cf222e; }
+.unknown { color: #777777; }
+figure { margin: 1em 0; }
+figcaption { color: #555555; margin-bottom: 0.5em; }
+";
+
+// How an answer compares with the known one.
+enum Status {
+    Correct,
+    Wrong(String),
+    Unknown,
+}
+
+// The outcome of running a day.
+struct Outcome {
+    input_hash: String,
+
+    // The answer of each part and how it compares with the known one.
+    answers: Vec<(Part, String, Status)>,
+
+    timings: Vec<Timing>,
+    figures: Vec<Figure>,
+}
+
+struct Section {
+    year: u32,
+    name: &'static str,
+    puzzle: Puzzle,
+
+    // The outcome, or the error that stopped the day.
+    outcome: std::result::Result<Outcome, String>,
+}
+
+impl Section {
+    fn anchor(&self) -> String {
+        format!("{}-{}", self.year, self.name)
+    }
+
+    // The overall status of the day as a class name, which is also shown.
+    fn status(&self) -> &'static str {
+        match &self.outcome {
+            Err(_) => "error",
+            Ok(outcome) => {
+                if outcome
+                    .answers
+                    .iter()
+                    .any(|(_, _, status)| matches!(status, Status::Wrong(_)))
+                {
+                    "wrong"
+                } else if outcome
+                    .answers
+                    .iter()
+                    .all(|(_, _, status)| matches!(status, Status::Correct))
+                {
+                    "ok"
+                } else {
+                    "unknown"
+                }
+            }
+        }
+    }
+}
+
+// Run a day on the given input: solve both parts, time every phase and draw the figures.
+fn run(
+    day: &Day,
+    data: &[u8],
+    answers: &Answers,
+    iterations: usize,
+    cache: Option<&Cache>,
+) -> Result<Outcome> {
+    let timings = bench::run(day, data, iterations, cache, false)?;
+    let input = day.load(data, cache)?;
+    let mut result = Vec::new();
+
+    for part in Part::all() {
+        let answer = day.solve(&*input, part)?;
+        let status = match answers.get(day.year(), day.name(), part) {
+            Some(expected) if expected == answer => Status::Correct,
+            Some(expected) => Status::Wrong(expected.to_string()),
+            None => Status::Unknown,
+        };
+
+        result.push((part, answer, status));
+    }
+
+    Ok(Outcome {
+        input_hash: hash::hex(data),
+        answers: result,
+        timings,
+        figures: day.figures(&*input),
+    })
+}
+
+// The time the default implementations take to parse the input and compute both parts. The
+// default implementation of a part is the first one timed.
+fn total(timings: &[Timing]) -> String {
+    let mut phases: Vec<&str> = Vec::new();
+    let mut total = Duration::from_secs(0);
+
+    for timing in timings {
+        if !phases.contains(&timing.phase.as_str()) {
+            phases.push(&timing.phase);
+            total += timing.statistics.median();
+        }
+    }
+
+    format_duration(total)
+}
+
+// A horizontal bar chart of the median time of every phase and implementation.
+fn timing_chart(timings: &[Timing]) -> String {
+    const LABEL_WIDTH: f64 = 170.0;
+    const BAR_WIDTH: f64 = 360.0;
+    const ROW_HEIGHT: f64 = 20.0;
+
+    let longest = timings
+        .iter()
+        .map(|timing| timing.statistics.median().as_secs_f64())
+        .fold(0.0, f64::max);
+    let mut svg = Svg::new(
+        LABEL_WIDTH + BAR_WIDTH + 90.0,
+        timings.len() as f64 * ROW_HEIGHT,
+    );
+
+    for (row, timing) in timings.iter().enumerate() {
+        let y = row as f64 * ROW_HEIGHT;
+        let median = timing.statistics.median();
+        let width = if longest > 0.0 {
+            (median.as_secs_f64() / longest * BAR_WIDTH).max(1.0)
+        } else {
+            1.0
+        };
+        let fill = match timing.phase.as_str() {
+            "parse" => "#999999",
+            "part 1" => "#4393c3",
+            _ => "#d6604d",
+        };
+        let label = format!("{} ({})", timing.phase, timing.implementation);
+
+        svg.text(LABEL_WIDTH - 8.0, y + 14.0, "end", &label);
+        svg.rect(
+            LABEL_WIDTH,
+            y + 3.0,
+            width,
+            ROW_HEIGHT - 6.0,
+            fill,
+            &timing.statistics.to_string(),
+        );
+        svg.text(
+            LABEL_WIDTH + width + 6.0,
+            y + 14.0,
+            "start",
+            &format_duration(median),
+        );
+    }
+
+    svg.finish()
+}
+
+// The report of a number of days, built up one day at a time.
+pub struct Report {
+    iterations: usize,
+    sections: Vec<Section>,
+}
+
+impl Report {
+    // A report timing every phase over the given number of iterations.
+    pub fn new(iterations: usize) -> Report {
+        Report {
+            iterations,
+            sections: Vec::new(),
+        }
+    }
+
+    // Run the given day on the given input and add it to the report. A day that fails is added
+    // to the report with its error, which is returned as well.
+    pub fn add(
+        &mut self,
+        day: &Day,
+        data: &[u8],
+        answers: &Answers,
+        cache: Option<&Cache>,
+    ) -> Result<()> {
+        let (outcome, result) = match run(day, data, answers, self.iterations, cache) {
+            Ok(outcome) => (Ok(outcome), Ok(())),
+            Err(e) => (Err(e.to_string()), Err(e)),
+        };
+
+        self.sections.push(Section {
+            year: day.year(),
+            name: day.name(),
+            puzzle: day.puzzle(),
+            outcome,
+        });
+
+        result
+    }
+
+    // The report as a complete HTML document.
+    pub fn html(&self) -> String {
+        let mut years: Vec<String> = self.sections.iter().map(|s| s.year.to_string()).collect();
+        years.dedup();
+
+        let title = format!("Advent of Code {}", years.join(", "));
+        let mut html = String::new();
+
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{title}</h1>\n\
+             <p>The timings are the median of {} iteration(s).</p>\n",
+            STYLE,
+            self.iterations,
+            title = escape(&title)
+        );
+
+        self.summary(&mut html);
+
+        for section in self.sections.iter() {
+            self.section(&mut html, section);
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    // A table with a row for every day, linking to its section.
+    fn summary(&self, html: &mut String) {
+        html.push_str(
+            "<table>\n<tr><th>Day</th><th>Puzzle</th><th>Part 1</th><th>Part 2</th>\
+             <th>Time</th><th>Status</th></tr>\n",
+        );
+
+        for section in self.sections.iter() {
+            let (answers, time) = match &section.outcome {
+                Ok(outcome) => (
+                    outcome
+                        .answers
+                        .iter()
+                        .map(|(_, answer, _)| {
+                            format!("<td class=\"answer\">{}</td>", escape(answer))
+                        })
+                        .collect::<String>(),
+                    total(&outcome.timings),
+                ),
+                Err(_) => (String::from("<td></td><td></td>"), String::new()),
+            };
+
+            let _ = writeln!(
+                html,
+                "<tr><td><a href=\"#{}\">{} {}</a></td><td>{}</td>{}<td>{}</td>\
+                 <td class=\"{status}\">{status}</td></tr>",
+                section.anchor(),
+                section.year,
+                section.name,
+                escape(section.puzzle.title),
+                answers,
+                time,
+                status = section.status()
+            );
+        }
+
+        html.push_str("</table>\n");
+    }
+
+    // The section of a single day.
+    fn section(&self, html: &mut String, section: &Section) {
+        let puzzle = &section.puzzle;
+
+        let _ = write!(
+            html,
+            "<section id=\"{}\">\n<h2>{} day {}: {}</h2>\n<p>Input: {}.</p>\n",
+            section.anchor(),
+            section.year,
+            puzzle.day,
+            escape(puzzle.title),
+            escape(puzzle.input)
+        );
+
+        let outcome = match &section.outcome {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                let _ = write!(
+                    html,
+                    "<p class=\"error\">error: {}</p>\n</section>\n",
+                    escape(e)
+                );
+                return;
+            }
+        };
+
+        html.push_str(
+            "<table>\n<tr><th>Part</th><th>Question</th><th>Answer</th><th>Known answer</th></tr>\n",
+        );
+
+        for (part, answer, status) in outcome.answers.iter() {
+            let known = match status {
+                Status::Correct => String::from("<td class=\"ok\">matches</td>"),
+                Status::Wrong(expected) => {
+                    format!("<td class=\"wrong\">differs: {}</td>", escape(expected))
+                }
+                Status::Unknown => String::from("<td class=\"unknown\">unknown</td>"),
+            };
+
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{} ({})</td><td class=\"answer\">{}</td>{}</tr>",
+                part,
+                escape(puzzle.part(*part)),
+                puzzle.answer(*part),
+                escape(answer),
+                known
+            );
+        }
+
+        let _ = write!(
+            html,
+            "</table>\n<figure>\n<figcaption>Median time of every phase and implementation, \
+             input {}</figcaption>\n{}\n</figure>\n",
+            outcome.input_hash,
+            timing_chart(&outcome.timings)
+        );
+
+        for figure in outcome.figures.iter() {
+            let _ = write!(
+                html,
+                "<figure>\n<figcaption>{}</figcaption>\n{}\n</figure>\n",
+                escape(&figure.title),
+                figure.svg
+            );
+        }
+
+        html.push_str("</section>\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use days;
+
+    #[test]
+    fn report_days() {
+        let all = days::all();
+        let day = |name: &str| &all[days::find(&all, 2018, name).unwrap()];
+        let answers = Answers::parse("2018 dec01 1 3\n2018 dec01 2 2\n2018 dec03 1 5\n").unwrap();
+
+        let mut report = Report::new(1);
+        report
+            .add(day("dec01"), b"+1\n-2\n+3\n+1\n", &answers, None)
+            .unwrap();
+        report
+            .add(
+                day("dec03"),
+                b"#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n",
+                &answers,
+                None,
+            )
+            .unwrap();
+        assert!(report
+            .add(
+                day("dec04"),
+                b"[1518-11-01 00:25] wakes up\n",
+                &answers,
+                None
+            )
+            .is_err());
+
+        let html = report.html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Advent of Code 2018</title>"));
+        assert!(html.contains("<a href=\"#2018-dec01\">2018 dec01</a></td><td>Chronal Calibration"));
+        assert!(html.contains("<h2>2018 day 3: No Matter How You Slice It</h2>"));
+
+        // The answers are compared with the known ones, where there are any.
+        assert!(html.contains("<td class=\"ok\">ok</td>"));
+        assert!(html.contains("<td class=\"wrong\">differs: 5</td>"));
+        assert!(html.contains("<td class=\"unknown\">unknown</td>"));
+        assert!(html.contains("<td class=\"error\">error</td>"));
+
+        // A timing chart for each day that ran, and the heat map of dec03.
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert!(html.contains("Claims covering the fabric"));
+
+        // Everything is embedded.
+        assert!(!html.contains("src="));
+        assert!(!html.contains("<script"));
+    }
+}
@@ -0,0 +1,389 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Scaffolding of new day crates.
+//
+// A new day starts out as a copy of the templates in `templates`, which follow the layout of
+// dec03 and dec04: the parsers and types in modules of their own, a stub solution for both parts
+// and an example test. The crate is then registered with the workspace and the runner by adding
+// the same lines the other days have next to theirs, in the order of the days.
+//
+// The days of the first year live in `src/decNN`, while those of later years live in
+// `src/<year>/decNN` and have the year in their package name, see `days::package()`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::{Error, Result};
+
+use days::{self, FIRST_YEAR};
+
+// The templates and where they end up inside the new crate.
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    (
+        "src/parsers.rs",
+        include_str!("../templates/parsers.rs.tmpl"),
+    ),
+    ("src/types.rs", include_str!("../templates/types.rs.tmpl")),
+    (
+        "src/metadata.rs",
+        include_str!("../templates/metadata.rs.tmpl"),
+    ),
+    ("src/cache.rs", include_str!("../templates/cache.rs.tmpl")),
+    (
+        "src/explore.rs",
+        include_str!("../templates/explore.rs.tmpl"),
+    ),
+    (
+        "src/generator.rs",
+        include_str!("../templates/generator.rs.tmpl"),
+    ),
+    (
+        "examples/01.in",
+        include_str!("../templates/example.in.tmpl"),
+    ),
+    (
+        "examples/01.out",
+        include_str!("../templates/example.out.tmpl"),
+    ),
+    ("input/data.txt", ""),
+];
+
+// The lines registering a day, by the file they are in relative to the root of the repository.
+// Each one is rendered like a template. The crates of the first year are declared and their
+// solutions imported by name. Later years skip those registrations, marked as being for the first
+// year only, and refer to their solutions by path instead: their types would clash with those of
+// the first year, and rustfmt would sort their crates away from the other days. Every line has
+// to be different for each year, or the days of other years would be taken for those of this
+// one.
+const REGISTRATIONS: &[(&str, &str, bool)] = &[
+    ("Cargo.toml", "    \"src/{{directory}}\",", false),
+    (
+        "src/runner/Cargo.toml",
+        "{{package}} = { path = \"../{{directory}}\" }",
+        false,
+    ),
+    ("src/runner/src/main.rs", "extern crate {{crate}};", true),
+    ("src/runner/src/days.rs", "use {{crate}}::{{type}};", true),
+    (
+        "src/runner/src/days.rs",
+        "        Day::new::<{{solution}}>({{year}}, {{number}}, \"{{name}}\", input!(\"{{directory}}\")),",
+        false,
+    ),
+];
+
+// Advent of Code has a puzzle for each of the first 25 days of December, and started in 2015.
+const LAST_DAY: u32 = 25;
+const FIRST_EVENT: u32 = 2015;
+
+// The last year we look for other days in when registering a day.
+const LAST_EVENT: u32 = 2100;
+
+// Parse a year of the event.
+pub fn parse_year(s: &str) -> Option<u32> {
+    let year = s.parse::<u32>().ok()?;
+
+    if (FIRST_EVENT..=LAST_EVENT).contains(&year) {
+        Some(year)
+    } else {
+        None
+    }
+}
+
+// Parse a day given either as a number or as the name of its crate.
+pub fn parse_day(s: &str) -> Option<u32> {
+    let number = s.strip_prefix("dec").unwrap_or(s).parse::<u32>().ok()?;
+
+    if (1..=LAST_DAY).contains(&number) {
+        Some(number)
+    } else {
+        None
+    }
+}
+
+// Fill in the placeholders of a template for the given day of the given year.
+pub fn render(template: &str, year: u32, number: u32) -> String {
+    let name = format!("dec{:02}", number);
+    let kind = format!("Dec{:02}", number);
+    let package = days::package(year, &name);
+    let krate = package.replace('-', "_");
+
+    // The binary needs the common crate and its own, in the order rustfmt sorts them in.
+    let mut externs = [String::from("common"), krate.clone()];
+    externs.sort();
+
+    let (common, solution) = if year == FIRST_YEAR {
+        ("../common", kind.clone())
+    } else {
+        ("../../common", format!("{}::{}", krate, kind))
+    };
+
+    template
+        .replace("{{name}}", &name)
+        .replace("{{type}}", &kind)
+        .replace("{{number}}", &number.to_string())
+        .replace("{{year}}", &year.to_string())
+        .replace("{{package}}", &package)
+        .replace("{{crate}}", &krate)
+        .replace("{{directory}}", &days::directory(year, &name))
+        .replace("{{common}}", common)
+        .replace("{{solution}}", &solution)
+        .replace(
+            "{{extern}}",
+            &format!("extern crate {};\nextern crate {};", externs[0], externs[1]),
+        )
+}
+
+// Add the line of a registration for the given day to the text of a file. The line is placed
+// after the same line of the closest earlier day, or before that of the closest later day if
+// there is no earlier one. The days of earlier years come before those of later years.
+pub fn register(text: &str, template: &str, year: u32, number: u32) -> Result<String> {
+    let line = render(template, year, number);
+    let mut lines: Vec<&str> = text.lines().collect();
+
+    if lines.contains(&line.as_str()) {
+        return Err(Error::invalid(format!(
+            "{} is already registered",
+            days::directory(year, &format!("dec{:02}", number))
+        )));
+    }
+
+    let position = |&(year, number): &(u32, u32)| {
+        let line = render(template, year, number);
+        lines.iter().position(|l| *l == line)
+    };
+
+    let others: Vec<(u32, u32)> = (FIRST_EVENT..=LAST_EVENT)
+        .flat_map(|year| (1..=LAST_DAY).map(move |number| (year, number)))
+        .collect();
+    let (earlier, later): (Vec<_>, Vec<_>) = others
+        .into_iter()
+        .filter(|other| *other != (year, number))
+        .partition(|other| *other < (year, number));
+
+    let index = match earlier.iter().rev().find_map(&position) {
+        Some(index) => index + 1,
+        None => match later.iter().find_map(&position) {
+            Some(index) => index,
+            None => {
+                return Err(Error::invalid(format!(
+                    "no other day is registered with '{}'",
+                    render(template, FIRST_YEAR, 1)
+                )))
+            }
+        },
+    };
+
+    lines.insert(index, &line);
+
+    let mut result = lines.join("\n");
+
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+
+    Ok(result)
+}
+
+// Create the crate of the given day of the given year in the repository at `root` and register
+// it, with the given title of the puzzle. Returns the files that were created and changed.
+pub fn create(root: &Path, year: u32, number: u32, title: &str) -> Result<Vec<PathBuf>> {
+    let name = format!("dec{:02}", number);
+    let directory = root.join("src").join(days::directory(year, &name));
+
+    if directory.exists() {
+        return Err(Error::invalid(format!(
+            "{} already exists",
+            directory.display()
+        )));
+    }
+
+    // Work out all the changes to the existing files first, so that nothing is written if one of
+    // them cannot be registered.
+    let mut changes: Vec<(PathBuf, String)> = Vec::new();
+
+    for (file, template, first_year_only) in REGISTRATIONS {
+        if *first_year_only && year != FIRST_YEAR {
+            continue;
+        }
+
+        let path = root.join(file);
+
+        let text = match changes.iter().position(|(p, _)| *p == path) {
+            Some(index) => changes.remove(index).1,
+            None => fs::read_to_string(&path)?,
+        };
+
+        let text = register(&text, template, year, number)
+            .map_err(|e| Error::invalid(format!("{}: {}", path.display(), e)))?;
+
+        changes.push((path, text));
+    }
+
+    let mut result = Vec::new();
+
+    for (file, template) in TEMPLATES {
+        let path = directory.join(file);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // The title goes into the metadata of the day as a string literal.
+        let text = render(template, year, number).replace("{{title}}", &format!("{:?}", title));
+
+        fs::write(&path, text)?;
+        result.push(path);
+    }
+
+    for (path, text) in changes {
+        fs::write(&path, text)?;
+        result.push(path);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn parse_days() {
+        assert_eq!(parse_day("6"), Some(6));
+        assert_eq!(parse_day("dec06"), Some(6));
+        assert_eq!(parse_day("25"), Some(25));
+        assert_eq!(parse_day("0"), None);
+        assert_eq!(parse_day("26"), None);
+        assert_eq!(parse_day("foo"), None);
+
+        assert_eq!(parse_year("2019"), Some(2019));
+        assert_eq!(parse_year("2014"), None);
+        assert_eq!(parse_year("19"), None);
+    }
+
+    #[test]
+    fn render_templates() {
+        for (_, template) in TEMPLATES {
+            let template = template.replace("{{title}}", "");
+
+            assert!(!render(&template, 2018, 6).contains("{{"));
+            assert!(!render(&template, 2019, 6).contains("{{"));
+        }
+
+        assert_eq!(
+            render(REGISTRATIONS[4].1, 2018, 7),
+            "        Day::new::<Dec07>(2018, 7, \"dec07\", input!(\"dec07\")),"
+        );
+        assert_eq!(
+            render(REGISTRATIONS[4].1, 2019, 7),
+            "        Day::new::<aoc2019_dec07::Dec07>(2019, 7, \"dec07\", input!(\"2019/dec07\")),"
+        );
+        assert_eq!(
+            render(REGISTRATIONS[1].1, 2019, 7),
+            "aoc2019-dec07 = { path = \"../2019/dec07\" }"
+        );
+        assert_eq!(
+            render("{{extern}}", 2019, 7),
+            "extern crate aoc2019_dec07;\nextern crate common;"
+        );
+    }
+
+    #[test]
+    fn register_in_order() {
+        let template = "extern crate {{crate}};";
+        let text =
+            "extern crate common;\nextern crate dec01;\nextern crate dec03;\nextern crate foo;\n";
+
+        assert_eq!(
+            register(text, template, 2018, 2).unwrap(),
+            "extern crate common;\nextern crate dec01;\nextern crate dec02;\nextern crate dec03;\nextern crate foo;\n"
+        );
+        assert_eq!(
+            register(text, template, 2018, 4).unwrap(),
+            "extern crate common;\nextern crate dec01;\nextern crate dec03;\nextern crate dec04;\nextern crate foo;\n"
+        );
+        assert!(register(text, template, 2018, 3).is_err());
+        assert!(register("extern crate foo;\n", template, 2018, 3).is_err());
+
+        let text = "extern crate dec02;";
+        assert_eq!(
+            register(text, template, 2018, 1).unwrap(),
+            "extern crate dec01;\nextern crate dec02;"
+        );
+
+        // Later years go after the earlier ones, whatever their day.
+        let text = "extern crate dec25;\nextern crate aoc2020_dec01;\n";
+        assert_eq!(
+            register(text, template, 2019, 3).unwrap(),
+            "extern crate dec25;\nextern crate aoc2019_dec03;\nextern crate aoc2020_dec01;\n"
+        );
+    }
+
+    #[test]
+    fn create_day() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        // Only the files the registrations go into, as they look for dec01.
+        let files = [
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\n    \"src/dec01\",\n]\n",
+            ),
+            ("src/runner/Cargo.toml", "dec01 = { path = \"../dec01\" }\n"),
+            ("src/runner/src/main.rs", "extern crate dec01;\n"),
+            (
+                "src/runner/src/days.rs",
+                "use dec01::Dec01;\n        Day::new::<Dec01>(2018, 1, \"dec01\", input!(\"dec01\")),\n",
+            ),
+        ];
+
+        for (file, text) in &files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+
+        let created = create(&root, 2018, 2, "Inventory \"Management\" System").unwrap();
+        assert_eq!(created.len(), TEMPLATES.len() + 4);
+
+        let lib = fs::read_to_string(root.join("src/dec02/src/lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Dec02"));
+
+        let metadata = fs::read_to_string(root.join("src/dec02/src/metadata.rs")).unwrap();
+        assert!(metadata.contains("title: \"Inventory \\\"Management\\\" System\","));
+
+        let days = fs::read_to_string(root.join("src/runner/src/days.rs")).unwrap();
+        assert!(days.contains("use dec02::Dec02;\n"));
+        assert!(days.ends_with("Day::new::<Dec02>(2018, 2, \"dec02\", input!(\"dec02\")),\n"));
+
+        // A later year gets a directory of its own and is not imported by name.
+        let created = create(&root, 2019, 1, "Day 1").unwrap();
+        assert_eq!(created.len(), TEMPLATES.len() + 3);
+
+        let manifest = fs::read_to_string(root.join("src/2019/dec01/Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"aoc2019-dec01\""));
+        assert!(manifest.contains("path = \"../../common\""));
+
+        let days = fs::read_to_string(root.join("src/runner/src/days.rs")).unwrap();
+        assert!(!days.contains("use aoc2019_dec01"));
+        assert!(days.contains("Day::new::<aoc2019_dec01::Dec01>(2019, 1"));
+        assert!(days.ends_with("input!(\"2019/dec01\")),\n"));
+
+        // The crate exists now, and an unregistered day leaves the tree alone.
+        assert!(create(&root, 2018, 2, "Day 2").is_err());
+        fs::write(root.join("src/runner/src/main.rs"), "").unwrap();
+        assert!(create(&root, 2018, 3, "Day 3").is_err());
+        assert!(!root.join("src/dec03").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use common::trace::Event;
+
+use output::{self, Record};
+
+// Where the trace events of a run end up.
+pub enum Tracer {
+    // Print the events to the standard error in a readable form.
+    Stderr,
+
+    // Save the events to a file as JSON lines, one object per event.
+    File(BufWriter<File>),
+}
+
+impl Tracer {
+    // Open the tracer for the given `--trace` argument, "-" meaning the standard error.
+    pub fn open(target: &str) -> io::Result<Tracer> {
+        match target {
+            "-" => Ok(Tracer::Stderr),
+            path => Ok(Tracer::File(BufWriter::new(File::create(path)?))),
+        }
+    }
+
+    // Write the events emitted by the given phase of a day.
+    pub fn write(&mut self, year: u32, day: &str, phase: &str, events: &[Event]) -> io::Result<()> {
+        for event in events {
+            match self {
+                Tracer::Stderr => eprintln!("  {} {}: {}", day, phase, event),
+                Tracer::File(writer) => {
+                    let mut record = Record::new()
+                        .integer("year", year)
+                        .string("day", day)
+                        .string("phase", phase)
+                        .string("event", event.message.as_str());
+
+                    for (name, value) in event.fields.iter() {
+                        record = record.string(name, value.as_str());
+                    }
+
+                    writeln!(writer, "{}", output::json_object(&record))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        match self {
+            Tracer::Stderr => Ok(()),
+            Tracer::File(mut writer) => writer.flush(),
+        }
+    }
+}
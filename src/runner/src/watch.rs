@@ -0,0 +1,442 @@
+// Copyright (c) 2018 Alexander Færøy. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+// Watch mode.
+//
+// The runner cannot reload the code of a day, so `aoc watch` leaves the work to cargo: it polls
+// the files of a day crate and its input, and whenever one of them changes it runs the examples
+// of the day with `aoc examples` and its input with `aoc run` in child processes, which rebuild
+// the day first. The answers and timings are compared with those of the previous run.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use bench::format_duration;
+use days::Day;
+
+// The modification time and size of every file below a set of paths.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl Snapshot {
+    // Paths that do not exist are skipped, such that they show up as added once they do.
+    pub fn take(paths: &[PathBuf]) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+
+        for path in paths {
+            snapshot.add(path);
+        }
+
+        snapshot
+    }
+
+    fn add(&mut self, path: &Path) {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(_) => return,
+        };
+
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    self.add(&entry.path());
+                }
+            }
+        } else {
+            let modified = metadata.modified().ok();
+            self.files
+                .insert(path.to_path_buf(), (modified, metadata.len()));
+        }
+    }
+
+    // The files that were added, removed or modified since the given snapshot.
+    pub fn changes(&self, previous: &Snapshot) -> Vec<PathBuf> {
+        let mut result: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, state)| previous.files.get(*path) != Some(state))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        result.extend(
+            previous
+                .files
+                .keys()
+                .filter(|path| !self.files.contains_key(*path))
+                .cloned(),
+        );
+
+        result.sort();
+        result
+    }
+}
+
+// The answer to a part and how long it took, as reported by `aoc run --format csv`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub part: String,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+// Split a line of the CSV output of the runner into its fields.
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut characters = line.chars().peekable();
+
+    while let Some(c) = characters.next() {
+        match c {
+            '"' if quoted && characters.peek() == Some(&'"') => {
+                characters.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    fields.push(field);
+    fields
+}
+
+// Read the answers from the CSV output of `aoc run`. Lines that do not have the expected fields
+// are ignored.
+pub fn parse_answers(csv: &str) -> Vec<Answer> {
+    let mut lines = csv.lines();
+
+    let header = match lines.next() {
+        Some(header) => split_csv(header),
+        None => return Vec::new(),
+    };
+    let column = |name: &str| header.iter().position(|field| field == name);
+
+    let (part, answer, elapsed) = match (column("part"), column("answer"), column("elapsed_ns")) {
+        (Some(part), Some(answer), Some(elapsed)) => (part, answer, elapsed),
+        _ => return Vec::new(),
+    };
+
+    lines
+        .filter_map(|line| {
+            let fields = split_csv(line);
+
+            Some(Answer {
+                part: fields.get(part)?.clone(),
+                answer: fields.get(answer)?.clone(),
+                elapsed: Duration::from_nanos(fields.get(elapsed)?.parse().ok()?),
+            })
+        })
+        .collect()
+}
+
+// Describe the answers of the current run, compared with the previous run if there was one.
+pub fn compare(previous: Option<&[Answer]>, current: &[Answer]) -> Vec<String> {
+    let mut result = Vec::new();
+
+    for answer in current {
+        let before = previous.and_then(|p| p.iter().find(|a| a.part == answer.part));
+        let elapsed = format_duration(answer.elapsed);
+
+        result.push(match before {
+            None => format!("part {}: {} in {}", answer.part, answer.answer, elapsed),
+            Some(before) => {
+                let change = if before.answer == answer.answer {
+                    String::from("unchanged")
+                } else {
+                    format!("was {}", before.answer)
+                };
+
+                // A part can take no measurable time at all, which leaves nothing to compare with.
+                let was = if before.elapsed == Duration::from_secs(0) {
+                    format!("was {}", format_duration(before.elapsed))
+                } else {
+                    let ratio = answer.elapsed.as_secs_f64() / before.elapsed.as_secs_f64() - 1.0;
+                    format!(
+                        "was {}, {:+.1}%",
+                        format_duration(before.elapsed),
+                        ratio * 100.0
+                    )
+                };
+
+                format!(
+                    "part {}: {} ({}) in {} ({})",
+                    answer.part, answer.answer, change, elapsed, was
+                )
+            }
+        });
+    }
+
+    for before in previous.unwrap_or(&[]) {
+        if !current.iter().any(|a| a.part == before.part) {
+            result.push(format!(
+                "part {}: no answer (was {})",
+                before.part, before.answer
+            ));
+        }
+    }
+
+    result
+}
+
+// Watches a single day and re-runs it on changes.
+pub struct Watcher {
+    // The root of the repository and the path to cargo.
+    root: PathBuf,
+    cargo: String,
+
+    // The day and the arguments selecting it in `aoc run`.
+    name: String,
+    day: String,
+
+    // The input given with --input, if any, and the paths we poll.
+    input: Option<String>,
+    paths: Vec<PathBuf>,
+
+    interval: Duration,
+    previous: Option<Vec<Answer>>,
+}
+
+impl Watcher {
+    pub fn new(
+        root: &Path,
+        cargo: &str,
+        day: &Day,
+        input: Option<&str>,
+        source: &Path,
+        interval: Duration,
+    ) -> Watcher {
+        let directory = root.join("src").join(day.directory());
+        let mut paths = vec![
+            directory.join("src"),
+            directory.join("examples"),
+            directory.join("input"),
+        ];
+
+        // The default input is inside the crate, but an input given elsewhere is polled as well.
+        let source = source
+            .canonicalize()
+            .unwrap_or_else(|_| source.to_path_buf());
+
+        if !source.starts_with(&directory) {
+            paths.push(source);
+        }
+
+        Watcher {
+            root: root.to_path_buf(),
+            cargo: cargo.to_string(),
+            name: day.name().to_string(),
+            day: format!("{}/{}", day.year(), day.number()),
+            input: input.map(String::from),
+            paths,
+            interval,
+            previous: None,
+        }
+    }
+
+    fn cargo(&self, arguments: &[&str]) -> Command {
+        let mut command = Command::new(&self.cargo);
+        command
+            .arg(arguments[0])
+            .arg("--quiet")
+            .arg("--manifest-path")
+            .arg(self.root.join("Cargo.toml"))
+            .args(&arguments[1..]);
+
+        command
+    }
+
+    // Run the examples of the day with `aoc examples`. Its output is only shown if it fails.
+    fn examples(&self) {
+        let output = self
+            .cargo(&["run", "--release", "--bin", "aoc", "--", "examples"])
+            .arg(&self.day)
+            .output();
+
+        match output {
+            Ok(ref output) if output.status.success() => println!("examples: ok"),
+            Ok(output) => {
+                println!("examples: FAILED");
+                print!("{}", String::from_utf8_lossy(&output.stdout));
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            }
+            Err(e) => println!("examples: cannot run {}: {}", self.cargo, e),
+        }
+    }
+
+    // Run the day on its input and compare the answers with the previous run. Build errors and
+    // errors of the day are shown as they are.
+    fn answers(&mut self) {
+        let mut arguments = vec!["run", "--release", "--bin", "aoc", "--", "run", &self.day];
+        arguments.extend(&["--format", "csv"]);
+
+        if let Some(input) = self.input.as_ref() {
+            arguments.extend(&["--input", input]);
+        }
+
+        let output = self.cargo(&arguments).stderr(Stdio::inherit()).output();
+
+        match output {
+            Ok(ref output) if output.status.success() => {
+                let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+
+                for line in compare(self.previous.as_deref(), &answers) {
+                    println!("{}", line);
+                }
+
+                self.previous = Some(answers);
+            }
+            Ok(_) => println!("{}: FAILED", self.name),
+            Err(e) => println!("{}: cannot run {}: {}", self.name, self.cargo, e),
+        }
+    }
+
+    // Poll for changes forever.
+    pub fn run(&mut self) -> ! {
+        let mut snapshot = Snapshot::take(&self.paths);
+        let mut changes: Vec<PathBuf> = Vec::new();
+
+        loop {
+            match changes.len() {
+                0 => println!("== {}", self.name),
+                1 => println!(
+                    "== {}: {} changed",
+                    self.name,
+                    self.relative(&changes[0]).display()
+                ),
+                n => println!(
+                    "== {}: {} and {} more changed",
+                    self.name,
+                    self.relative(&changes[0]).display(),
+                    n - 1
+                ),
+            }
+
+            self.examples();
+            self.answers();
+            println!();
+
+            // Wait for the next change. Files written while we were running count as well.
+            loop {
+                let next = Snapshot::take(&self.paths);
+                changes = next.changes(&snapshot);
+                snapshot = next;
+
+                if !changes.is_empty() {
+                    break;
+                }
+
+                thread::sleep(self.interval);
+            }
+        }
+    }
+
+    fn relative<'a>(&self, path: &'a Path) -> &'a Path {
+        path.strip_prefix(&self.root).unwrap_or(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn snapshot_changes() {
+        let directory = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(directory.join("src")).unwrap();
+        fs::write(directory.join("src/lib.rs"), "a").unwrap();
+        fs::write(directory.join("src/types.rs"), "b").unwrap();
+
+        let paths = vec![directory.join("src"), directory.join("input")];
+        let first = Snapshot::take(&paths);
+        assert!(Snapshot::take(&paths).changes(&first).is_empty());
+
+        fs::write(directory.join("src/lib.rs"), "ab").unwrap();
+        fs::remove_file(directory.join("src/types.rs")).unwrap();
+        fs::create_dir_all(directory.join("input")).unwrap();
+        fs::write(directory.join("input/data.txt"), "1").unwrap();
+
+        assert_eq!(
+            Snapshot::take(&paths).changes(&first),
+            vec![
+                directory.join("input/data.txt"),
+                directory.join("src/lib.rs"),
+                directory.join("src/types.rs"),
+            ]
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn parse_csv_answers() {
+        let csv = "day,part,answer,implementation,elapsed_ns,input_hash\n\
+                   dec02,1,8715,default,1500,ab\n\
+                   dec02,2,\"a,\"\"b\",linear,2000,ab\n\
+                   dec02,x\n";
+
+        assert_eq!(
+            parse_answers(csv),
+            vec![
+                Answer {
+                    part: String::from("1"),
+                    answer: String::from("8715"),
+                    elapsed: Duration::from_nanos(1500),
+                },
+                Answer {
+                    part: String::from("2"),
+                    answer: String::from("a,\"b"),
+                    elapsed: Duration::from_nanos(2000),
+                },
+            ]
+        );
+        assert!(parse_answers("").is_empty());
+        assert!(parse_answers("day,part\ndec01,1\n").is_empty());
+    }
+
+    #[test]
+    fn compare_answers() {
+        let answer = |part: &str, answer: &str, micros| Answer {
+            part: String::from(part),
+            answer: String::from(answer),
+            elapsed: Duration::from_micros(micros),
+        };
+
+        let previous = vec![answer("1", "587", 100), answer("2", "83130", 200)];
+        let current = vec![answer("1", "587", 50), answer("2", "83131", 300)];
+
+        assert_eq!(
+            compare(None, &current[..1]),
+            vec!["part 1: 587 in 50.000µs"]
+        );
+        assert_eq!(
+            compare(Some(&previous), &current),
+            vec![
+                "part 1: 587 (unchanged) in 50.000µs (was 100.000µs, -50.0%)",
+                "part 2: 83131 (was 83130) in 300.000µs (was 200.000µs, +50.0%)",
+            ]
+        );
+        assert_eq!(
+            compare(Some(&previous), &current[..1])[1],
+            "part 2: no answer (was 83130)"
+        );
+
+        // No percentage is shown if the previous run took no measurable time.
+        assert_eq!(
+            compare(Some(&[answer("1", "587", 0)]), &current[..1]),
+            vec!["part 1: 587 (unchanged) in 50.000µs (was 0ns)"]
+        );
+    }
+}